@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+
+use crate::RhythmRegistry;
+
+/// Fixed-rate simulation clock shared by all registered rhythms.
+///
+/// Every tick advances the rhythms by the same simulated step,
+/// `time_scale / tick_rate` seconds, so the trajectory does not depend on how
+/// often a frontend happens to render.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SimulationClock {
+    /// Ticks per real-time second.
+    pub tick_rate: f64,
+    /// Simulated seconds per real-time second.
+    pub time_scale: f64,
+    pub paused: bool,
+    /// Total simulated time in seconds.
+    pub sim_time: f64,
+    pub tick_count: u64,
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        Self {
+            tick_rate: 60.0,
+            time_scale: 1.0,
+            paused: false,
+            sim_time: 0.0,
+            tick_count: 0,
+        }
+    }
+}
+
+impl SimulationClock {
+    pub const MAX_TICK_RATE: f64 = 1000.0;
+    pub const MAX_TIME_SCALE: f64 = 100.0;
+    /// Most ticks a single `step` may run, so one request cannot hold the
+    /// clock for long.
+    pub const MAX_STEPS: u32 = 10_000;
+
+    /// Real time between ticks, in seconds.
+    pub fn tick_interval(&self) -> f64 {
+        1.0 / self.tick_rate
+    }
+
+    /// Simulated time advanced by one tick, in seconds.
+    pub fn step_size(&self) -> f64 {
+        self.time_scale / self.tick_rate
+    }
+
+    pub fn set_tick_rate(&mut self, tick_rate: f64) -> Result<(), String> {
+        if !(tick_rate > 0.0 && tick_rate <= Self::MAX_TICK_RATE) {
+            return Err(format!(
+                "Tick rate must be in (0, {}] Hz, got {}",
+                Self::MAX_TICK_RATE,
+                tick_rate
+            ));
+        }
+        self.tick_rate = tick_rate;
+        Ok(())
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f64) -> Result<(), String> {
+        if !(0.0..=Self::MAX_TIME_SCALE).contains(&time_scale) {
+            return Err(format!(
+                "Time scale must be in [0, {}], got {}",
+                Self::MAX_TIME_SCALE,
+                time_scale
+            ));
+        }
+        self.time_scale = time_scale;
+        Ok(())
+    }
+
+    /// Advance every rhythm in `registry` by one tick.
+    pub fn tick(&mut self, registry: &RhythmRegistry) {
+        let delta_time = self.step_size();
+        registry.update_all(delta_time);
+        self.sim_time += delta_time;
+        self.tick_count += 1;
+    }
+
+    /// Advance by `steps` ticks regardless of the paused flag, calling
+    /// `after_tick` after each one.
+    pub fn step(
+        &mut self,
+        registry: &RhythmRegistry,
+        steps: u32,
+        mut after_tick: impl FnMut(&SimulationClock),
+    ) -> Result<(), String> {
+        if steps > Self::MAX_STEPS {
+            return Err(format!("Steps must be at most {}, got {}", Self::MAX_STEPS, steps));
+        }
+        for _ in 0..steps {
+            self.tick(registry);
+            after_tick(self);
+        }
+        Ok(())
+    }
+}
+
+/// Simulated time kept as whole seconds plus a fraction, so it stays exact to
+/// well under a nanosecond however long a run lasts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "ElapsedRepr")]
+pub struct ElapsedTime {
+    pub seconds: u64,
+    /// Fraction of the current second, in [0, 1).
+    pub fraction: f64,
+}
+
+impl ElapsedTime {
+    pub fn advance(&mut self, delta_time: f64) {
+        let total = self.fraction + delta_time.max(0.0);
+        let whole = total.floor();
+        self.seconds += whole as u64;
+        self.fraction = total - whole;
+    }
+
+    pub fn as_secs_f64(&self) -> f64 {
+        self.seconds as f64 + self.fraction
+    }
+}
+
+/// Accepts the plain seconds written by versions that stored time as `f64`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ElapsedRepr {
+    Split { seconds: u64, fraction: f64 },
+    Seconds(f64),
+}
+
+impl From<ElapsedRepr> for ElapsedTime {
+    fn from(repr: ElapsedRepr) -> Self {
+        match repr {
+            ElapsedRepr::Split { seconds, fraction } => Self { seconds, fraction },
+            ElapsedRepr::Seconds(seconds) => {
+                let mut elapsed = Self::default();
+                elapsed.advance(seconds);
+                elapsed
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn elapsed_time_stays_exact_over_many_ticks() {
+        let mut elapsed = ElapsedTime::default();
+        let ticks = 60 * 60 * 60 * 24;
+        for _ in 0..ticks {
+            elapsed.advance(1.0 / 60.0);
+        }
+        assert_eq!(elapsed.seconds, 86_400);
+        assert!(elapsed.fraction.min(1.0 - elapsed.fraction) < 1e-9);
+        assert!((elapsed.as_secs_f64() - 86_400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn elapsed_time_reads_plain_seconds() {
+        let elapsed: ElapsedTime = serde_json::from_str("2.5").unwrap();
+        assert_eq!(elapsed, ElapsedTime { seconds: 2, fraction: 0.5 });
+    }
+}
@@ -0,0 +1,192 @@
+use serde::{Deserialize, Serialize};
+
+/// Numerical scheme used to advance a rhythm's continuous state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationScheme {
+    /// Explicit forward Euler.
+    Euler,
+    /// Classical fourth-order Runge-Kutta.
+    Rk4,
+    /// Symplectic Euler for second-order systems; an in-place Gauss-Seidel
+    /// sweep for first-order ones.
+    SemiImplicit,
+}
+
+impl IntegrationScheme {
+    /// Advance `y' = f(y)` by one step of size `h`.
+    ///
+    /// `f(y, i)` returns the derivative of component `i`.
+    pub fn step_first_order<F>(self, y: &mut [f64], h: f64, mut f: F)
+    where
+        F: FnMut(&[f64], usize) -> f64,
+    {
+        let n = y.len();
+        match self {
+            IntegrationScheme::Euler => {
+                let dy: Vec<f64> = (0..n).map(|i| f(y, i)).collect();
+                for (yi, dyi) in y.iter_mut().zip(dy) {
+                    *yi += h * dyi;
+                }
+            }
+            IntegrationScheme::Rk4 => {
+                let y0 = y.to_vec();
+                let k1: Vec<f64> = (0..n).map(|i| f(&y0, i)).collect();
+                let y1: Vec<f64> = (0..n).map(|i| y0[i] + 0.5 * h * k1[i]).collect();
+                let k2: Vec<f64> = (0..n).map(|i| f(&y1, i)).collect();
+                let y2: Vec<f64> = (0..n).map(|i| y0[i] + 0.5 * h * k2[i]).collect();
+                let k3: Vec<f64> = (0..n).map(|i| f(&y2, i)).collect();
+                let y3: Vec<f64> = (0..n).map(|i| y0[i] + h * k3[i]).collect();
+                let k4: Vec<f64> = (0..n).map(|i| f(&y3, i)).collect();
+                for i in 0..n {
+                    y[i] = y0[i] + h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+                }
+            }
+            IntegrationScheme::SemiImplicit => {
+                for i in 0..n {
+                    let dyi = f(y, i);
+                    y[i] += h * dyi;
+                }
+            }
+        }
+    }
+
+    /// Advance `x'' = a(x, v)` by one step of size `h`.
+    ///
+    /// `a(x, v, i)` returns the acceleration of component `i`.
+    pub fn step_second_order<A>(self, x: &mut [f64], v: &mut [f64], h: f64, mut a: A)
+    where
+        A: FnMut(&[f64], &[f64], usize) -> f64,
+    {
+        let n = x.len();
+        match self {
+            IntegrationScheme::Euler => {
+                let dv: Vec<f64> = (0..n).map(|i| a(x, v, i)).collect();
+                for i in 0..n {
+                    x[i] += h * v[i];
+                    v[i] += h * dv[i];
+                }
+            }
+            IntegrationScheme::Rk4 => {
+                // Integrate the stacked state [x, v] with y' = [v, a(x, v)]
+                let mut y = [x.to_vec(), v.to_vec()].concat();
+                IntegrationScheme::Rk4.step_first_order(&mut y, h, |y, i| {
+                    let (x, v) = y.split_at(n);
+                    if i < n {
+                        v[i]
+                    } else {
+                        a(x, v, i - n)
+                    }
+                });
+                x.copy_from_slice(&y[..n]);
+                v.copy_from_slice(&y[n..]);
+            }
+            IntegrationScheme::SemiImplicit => {
+                let dv: Vec<f64> = (0..n).map(|i| a(x, v, i)).collect();
+                for i in 0..n {
+                    v[i] += h * dv[i];
+                    x[i] += h * v[i];
+                }
+            }
+        }
+    }
+}
+
+/// Splits arbitrary frame times into fixed integration steps.
+///
+/// Time that does not fill a whole step is carried over to the next call, so
+/// the trajectory is the same however the caller slices time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Integrator {
+    pub scheme: IntegrationScheme,
+    step: f64,
+    accumulator: f64,
+}
+
+impl Integrator {
+    pub const DEFAULT_STEP: f64 = 1.0 / 120.0;
+
+    /// Upper bound on steps taken per call. Time beyond it is dropped so a
+    /// huge `delta_time` cannot stall the simulation.
+    pub const MAX_SUBSTEPS: usize = 10_000;
+
+    pub fn new(scheme: IntegrationScheme, step: f64) -> Self {
+        Self {
+            scheme,
+            step,
+            accumulator: 0.0,
+        }
+    }
+
+    /// Fixed step size in seconds.
+    pub fn step(&self) -> f64 {
+        self.step
+    }
+
+    /// Check an integrator read back from saved state.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.step > 0.0 && self.step.is_finite()) {
+            return Err(format!("Integrator step must be positive, got {}", self.step));
+        }
+        if !(self.accumulator >= 0.0 && self.accumulator.is_finite()) {
+            return Err(format!("Integrator accumulator must not be negative, got {}", self.accumulator));
+        }
+        Ok(())
+    }
+
+    /// Accumulate `delta_time` and return how many whole steps are due.
+    pub fn substeps(&mut self, delta_time: f64) -> usize {
+        self.accumulator += delta_time.max(0.0);
+        let due = (self.accumulator / self.step).floor();
+        if due >= Self::MAX_SUBSTEPS as f64 {
+            self.accumulator = 0.0;
+            return Self::MAX_SUBSTEPS;
+        }
+        self.accumulator -= due * self.step;
+        due as usize
+    }
+}
+
+impl Default for Integrator {
+    fn default() -> Self {
+        Self::new(IntegrationScheme::SemiImplicit, Self::DEFAULT_STEP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Error at t = 1 of `y' = -y`, `y(0) = 1`, integrated in `steps` steps.
+    fn decay_error(scheme: IntegrationScheme, steps: usize) -> f64 {
+        let h = 1.0 / steps as f64;
+        let mut y = [1.0];
+        for _ in 0..steps {
+            scheme.step_first_order(&mut y, h, |y, i| -y[i]);
+        }
+        (y[0] - (-1.0f64).exp()).abs()
+    }
+
+    #[test]
+    fn rk4_converges_at_fourth_order() {
+        let coarse = decay_error(IntegrationScheme::Rk4, 10);
+        let fine = decay_error(IntegrationScheme::Rk4, 20);
+        let order = (coarse / fine).log2();
+        assert!((order - 4.0).abs() < 0.1, "observed order {}", order);
+    }
+
+    #[test]
+    fn euler_converges_at_first_order() {
+        let coarse = decay_error(IntegrationScheme::Euler, 100);
+        let fine = decay_error(IntegrationScheme::Euler, 200);
+        let order = (coarse / fine).log2();
+        assert!((order - 1.0).abs() < 0.05, "observed order {}", order);
+    }
+
+    #[test]
+    fn substeps_carry_leftover_time() {
+        let mut integrator = Integrator::new(IntegrationScheme::Euler, 0.1);
+        let total: usize = (0..30).map(|_| integrator.substeps(0.035)).sum();
+        assert_eq!(total, 10);
+    }
+}
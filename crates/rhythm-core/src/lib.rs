@@ -0,0 +1,62 @@
+//! Simulation models behind the inner rhythm visualizer.
+//!
+//! This crate has no UI or Tauri dependency so the dynamics can be driven
+//! from tests, command line tools or headless services.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+pub mod clock;
+pub mod integrator;
+pub mod recording;
+pub mod rhythms;
+pub mod rng;
+pub mod snapshot;
+pub mod source;
+
+pub use clock::{ElapsedTime, SimulationClock};
+pub use integrator::{IntegrationScheme, Integrator};
+pub use rhythms::*;
+pub use recording::{RecordingHeader, ReplayStatus, SessionRecorder, SessionReplay};
+pub use snapshot::SimulationSnapshot;
+pub use source::FrameSource;
+
+/// A single frame of rhythm output.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RhythmData {
+    pub rhythm_type: String,
+    pub timestamp: f64,
+    pub values: Vec<f64>,
+    pub metadata: serde_json::Value,
+}
+
+/// A frame whose values are keyed by channel name instead of position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LabeledRhythmData {
+    pub rhythm_type: String,
+    pub timestamp: f64,
+    pub values: BTreeMap<String, f64>,
+    pub metadata: serde_json::Value,
+}
+
+impl RhythmData {
+    /// Attach channel names from `schema` to the values of this frame.
+    ///
+    /// Values beyond the channels described by the schema are dropped.
+    pub fn labeled(self, schema: &OutputSchema) -> LabeledRhythmData {
+        let values = schema
+            .channels
+            .iter()
+            .zip(self.values)
+            .map(|(channel, value)| (channel.name.clone(), value))
+            .collect();
+
+        LabeledRhythmData {
+            rhythm_type: self.rhythm_type,
+            timestamp: self.timestamp,
+            values,
+            metadata: self.metadata,
+        }
+    }
+}
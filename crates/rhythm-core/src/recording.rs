@@ -0,0 +1,337 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::source::FrameSource;
+use crate::{OutputSchema, RhythmData, RhythmDescription, RhythmRegistry, SimulationClock};
+
+const FORMAT: &str = "inner-rhythm-session";
+
+/// Configuration of one rhythm at the start of a recording.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedRhythm {
+    pub seed: u64,
+    pub params: serde_json::Value,
+    pub description: RhythmDescription,
+}
+
+/// First line of a session file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordingHeader {
+    pub format: String,
+    pub version: u32,
+    pub app_version: String,
+    pub created_by: String,
+    /// Wall-clock time the recording started, in seconds since the Unix epoch.
+    pub created_at: f64,
+    pub master_seed: u64,
+    pub clock: SimulationClock,
+    pub rhythms: BTreeMap<String, RecordedRhythm>,
+}
+
+impl RecordingHeader {
+    pub const VERSION: u32 = 1;
+
+    /// Describe the recorded rhythms as they are configured right now.
+    pub fn capture(
+        registry: &RhythmRegistry,
+        clock: &SimulationClock,
+        rhythm_ids: &[String],
+        app_version: &str,
+        created_at: f64,
+    ) -> Result<Self, String> {
+        let mut rhythms = BTreeMap::new();
+        for id in rhythm_ids {
+            let rhythm = registry.get(id)?;
+            let rhythm = rhythm.lock().unwrap();
+            rhythms.insert(
+                id.clone(),
+                RecordedRhythm {
+                    seed: rhythm.seed(),
+                    params: rhythm.params(),
+                    description: rhythm.describe(),
+                },
+            );
+        }
+
+        Ok(Self {
+            format: FORMAT.to_string(),
+            version: Self::VERSION,
+            app_version: app_version.to_string(),
+            created_by: format!("rhythm-core {}", env!("CARGO_PKG_VERSION")),
+            created_at,
+            master_seed: registry.master_seed(),
+            clock: clock.clone(),
+            rhythms,
+        })
+    }
+}
+
+/// One frame of a session file, tagged with the simulation tick it came from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedFrame {
+    pub tick: u64,
+    pub sim_time: f64,
+    pub frame: RhythmData,
+}
+
+/// Writes every frame of a session to a JSON Lines file.
+pub struct SessionRecorder {
+    writer: BufWriter<File>,
+    rhythm_ids: Vec<String>,
+    frames_written: u64,
+}
+
+impl SessionRecorder {
+    /// Create the file at `path` and write the header for `rhythm_ids`.
+    pub fn create(path: impl AsRef<Path>, header: &RecordingHeader) -> Result<Self, String> {
+        let file = File::create(path).map_err(|e| e.to_string())?;
+        let mut recorder = Self {
+            writer: BufWriter::new(file),
+            rhythm_ids: header.rhythms.keys().cloned().collect(),
+            frames_written: 0,
+        };
+        recorder.write_line(header)?;
+        Ok(recorder)
+    }
+
+    /// Append the current frame of every recorded rhythm.
+    pub fn record(&mut self, registry: &RhythmRegistry, clock: &SimulationClock, timestamp: f64) -> Result<(), String> {
+        for id in self.rhythm_ids.clone() {
+            let frame = RecordedFrame {
+                tick: clock.tick_count,
+                sim_time: clock.sim_time,
+                frame: registry.frame(&id, timestamp)?,
+            };
+            self.write_line(&frame)?;
+            self.frames_written += 1;
+        }
+        Ok(())
+    }
+
+    pub fn frames_written(&self) -> u64 {
+        self.frames_written
+    }
+
+    /// Flush buffered frames to disk.
+    pub fn finish(mut self) -> Result<u64, String> {
+        self.writer.flush().map_err(|e| e.to_string())?;
+        Ok(self.frames_written)
+    }
+
+    fn write_line<T: Serialize>(&mut self, value: &T) -> Result<(), String> {
+        serde_json::to_writer(&mut self.writer, value).map_err(|e| e.to_string())?;
+        self.writer.write_all(b"\n").map_err(|e| e.to_string())
+    }
+}
+
+/// Position and settings of a replay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayStatus {
+    pub position: f64,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub speed: f64,
+    pub looping: bool,
+    pub paused: bool,
+}
+
+/// Plays a recorded session back in simulated time.
+///
+/// Playback is paced by the recorded ticks, so changes of time scale during
+/// the session replay as they happened.
+pub struct SessionReplay {
+    pub header: RecordingHeader,
+    frames: BTreeMap<String, Vec<RecordedFrame>>,
+    /// Simulated time of every recorded tick, in tick order.
+    timeline: Vec<(u64, f64)>,
+    start_time: f64,
+    end_time: f64,
+    /// Cursor in ticks, between the first and last recorded tick.
+    cursor: f64,
+    /// Simulated time at the cursor.
+    position: f64,
+    speed: f64,
+    looping: bool,
+    paused: bool,
+    ticks: u64,
+}
+
+impl SessionReplay {
+    pub const MAX_SPEED: f64 = 100.0;
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| e.to_string())?;
+        let mut lines = BufReader::new(file).lines();
+
+        let header_line = lines
+            .next()
+            .ok_or_else(|| "Recording is empty".to_string())?
+            .map_err(|e| e.to_string())?;
+        let header: RecordingHeader = serde_json::from_str(&header_line).map_err(|e| e.to_string())?;
+        if header.format != FORMAT || header.version != RecordingHeader::VERSION {
+            return Err(format!(
+                "Unsupported recording format {} version {}",
+                header.format, header.version
+            ));
+        }
+        header.clock.clone().set_tick_rate(header.clock.tick_rate)?;
+
+        let mut frames: BTreeMap<String, Vec<RecordedFrame>> = BTreeMap::new();
+        for (index, line) in lines.enumerate() {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let frame: RecordedFrame =
+                serde_json::from_str(&line).map_err(|e| format!("Line {}: {}", index + 2, e))?;
+            frames.entry(frame.frame.rhythm_type.clone()).or_default().push(frame);
+        }
+
+        if frames.is_empty() {
+            return Err("Recording contains no frames".to_string());
+        }
+        let mut timeline: Vec<(u64, f64)> = frames.values().flatten().map(|frame| (frame.tick, frame.sim_time)).collect();
+        timeline.sort_by_key(|&(tick, _)| tick);
+        timeline.dedup_by_key(|&mut (tick, _)| tick);
+        for frames in frames.values_mut() {
+            frames.sort_by_key(|frame| frame.tick);
+        }
+        let times = timeline.iter().map(|&(_, sim_time)| sim_time);
+        let start_time = times.clone().fold(f64::INFINITY, f64::min);
+        let end_time = times.fold(f64::NEG_INFINITY, f64::max);
+        let (first_tick, position) = timeline[0];
+
+        Ok(Self {
+            header,
+            frames,
+            timeline,
+            start_time,
+            end_time,
+            cursor: first_tick as f64,
+            position,
+            speed: 1.0,
+            looping: false,
+            paused: false,
+            ticks: 0,
+        })
+    }
+
+    pub fn status(&self) -> ReplayStatus {
+        ReplayStatus {
+            position: self.position,
+            start_time: self.start_time,
+            end_time: self.end_time,
+            speed: self.speed,
+            looping: self.looping,
+            paused: self.paused,
+        }
+    }
+
+    /// Number of `advance` calls that moved the cursor, for frame pacing.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Move the cursor by `real_time` seconds of playback.
+    ///
+    /// At speed 1 the recorded ticks play at the tick rate the session was
+    /// recorded with, whatever time scale each tick had. Without looping,
+    /// playback stops at the last frame.
+    pub fn advance(&mut self, real_time: f64) {
+        if self.paused {
+            return;
+        }
+        self.ticks += 1;
+        self.cursor += real_time * self.speed * self.header.clock.tick_rate;
+
+        let first = self.timeline[0].0 as f64;
+        let last = self.timeline[self.timeline.len() - 1].0 as f64;
+        if self.cursor > last {
+            if self.looping && last > first {
+                self.cursor = first + (self.cursor - first) % (last - first);
+            } else {
+                self.cursor = last;
+                self.paused = true;
+            }
+        }
+        self.position = self.time_at(self.cursor);
+    }
+
+    /// Move the cursor to the first tick at simulated time `position`.
+    pub fn seek(&mut self, position: f64) -> Result<(), String> {
+        if !(self.start_time..=self.end_time).contains(&position) {
+            return Err(format!(
+                "Position must be in [{}, {}] s, got {}",
+                self.start_time, self.end_time, position
+            ));
+        }
+        let index = self.timeline.partition_point(|&(_, sim_time)| sim_time < position);
+        self.cursor = match (index.checked_sub(1).map(|i| self.timeline[i]), self.timeline.get(index)) {
+            (Some((tick0, time0)), Some(&(tick1, time1))) if time1 > time0 => {
+                tick0 as f64 + (tick1 - tick0) as f64 * (position - time0) / (time1 - time0)
+            }
+            (_, Some(&(tick, _))) => tick as f64,
+            (Some((tick, _)), None) => tick as f64,
+            (None, None) => self.cursor,
+        };
+        self.position = position;
+        Ok(())
+    }
+
+    /// Simulated time at `cursor`, interpolated between recorded ticks.
+    fn time_at(&self, cursor: f64) -> f64 {
+        let index = self.timeline.partition_point(|&(tick, _)| tick as f64 <= cursor);
+        match (index.checked_sub(1).map(|i| self.timeline[i]), self.timeline.get(index)) {
+            (Some((tick0, time0)), Some(&(tick1, time1))) => {
+                time0 + (time1 - time0) * (cursor - tick0 as f64) / (tick1 - tick0) as f64
+            }
+            (Some((_, time)), None) | (None, Some(&(_, time))) => time,
+            (None, None) => self.position,
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: f64) -> Result<(), String> {
+        if !(speed > 0.0 && speed <= Self::MAX_SPEED) {
+            return Err(format!("Replay speed must be in (0, {}], got {}", Self::MAX_SPEED, speed));
+        }
+        self.speed = speed;
+        Ok(())
+    }
+
+    pub fn set_looping(&mut self, looping: bool) {
+        self.looping = looping;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// Latest recorded frame of `rhythm_type` at or before the cursor.
+    fn current(&self, rhythm_type: &str) -> Result<&RecordedFrame, String> {
+        let frames = self
+            .frames
+            .get(rhythm_type)
+            .ok_or_else(|| format!("Rhythm {} is not in this recording", rhythm_type))?;
+        let index = frames.partition_point(|frame| frame.tick as f64 <= self.cursor);
+        Ok(&frames[index.saturating_sub(1)])
+    }
+}
+
+impl FrameSource for SessionReplay {
+    /// Frames are returned exactly as recorded, original timestamp included.
+    fn frame(&self, rhythm_type: &str, _timestamp: f64) -> Result<RhythmData, String> {
+        Ok(self.current(rhythm_type)?.frame.clone())
+    }
+
+    fn output_schema(&self, rhythm_type: &str) -> Result<OutputSchema, String> {
+        self.header
+            .rhythms
+            .get(rhythm_type)
+            .map(|rhythm| rhythm.description.output.clone())
+            .ok_or_else(|| format!("Rhythm {} is not in this recording", rhythm_type))
+    }
+}
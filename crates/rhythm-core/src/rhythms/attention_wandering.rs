@@ -0,0 +1,246 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{ChannelSpec, FieldType, MetadataField, OutputSchema, ParameterSpec, Rhythm, RhythmParams};
+use crate::RhythmData;
+use crate::rng::{random_seed, rng_from_seed, RhythmRng};
+use rand::Rng;
+use std::collections::HashMap;
+use std::any::Any;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AttentionWanderingParams {
+    /// Rate at which boredom builds up, in 1/s.
+    pub boredom_rate: f64,
+    /// Longest time attention stays on one target, in seconds.
+    pub max_focus_duration: f64,
+}
+
+impl Default for AttentionWanderingParams {
+    fn default() -> Self {
+        Self {
+            boredom_rate: 0.1,
+            max_focus_duration: 5.0,
+        }
+    }
+}
+
+impl RhythmParams for AttentionWanderingParams {
+    fn schema() -> Vec<ParameterSpec> {
+        let defaults = Self::default();
+        vec![
+            ParameterSpec::new("boredom_rate", "Rate at which boredom builds up", defaults.boredom_rate, 0.0, 10.0, "1/s"),
+            ParameterSpec::new("max_focus_duration", "Longest time attention stays on one target", defaults.max_focus_duration, 0.1, 60.0, "s"),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct AttentionWandering {
+    params: AttentionWanderingParams,
+    attention_position: (f64, f64),
+    attention_targets: Vec<(f64, f64)>,
+    boredom_level: f64,
+    curiosity_map: HashMap<String, f64>,
+    current_focus: Option<usize>,
+    focus_duration: f64,
+    seed: u64,
+    rng: RhythmRng,
+}
+
+impl Default for AttentionWandering {
+    fn default() -> Self {
+        Self::from_seed(random_seed())
+    }
+}
+
+impl AttentionWandering {
+    /// Create the rhythm with all randomness drawn from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = rng_from_seed(seed);
+        
+        // Create random attention targets
+        let targets = (0..5).map(|_| {
+            (
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+        }).collect();
+        
+        let mut curiosity_map = HashMap::new();
+        curiosity_map.insert("novel".to_string(), 0.8);
+        curiosity_map.insert("familiar".to_string(), 0.2);
+        curiosity_map.insert("complex".to_string(), 0.6);
+        
+        Self {
+            params: AttentionWanderingParams::default(),
+            attention_position: (0.0, 0.0),
+            attention_targets: targets,
+            boredom_level: 0.0,
+            curiosity_map,
+            current_focus: None,
+            focus_duration: 0.0,
+            seed,
+            rng,
+        }
+    }
+}
+
+impl Rhythm for AttentionWandering {
+    fn name(&self) -> &'static str {
+        "attention_wandering"
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        let params = self.params.clone();
+        *self = Self::from_seed(seed);
+        self.params = params;
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        let rng = &mut self.rng;
+
+        // Increase boredom over time
+        self.boredom_level += delta_time * self.params.boredom_rate;
+        
+        if let Some(focus_idx) = self.current_focus {
+            // Currently focused on a target
+            self.focus_duration += delta_time;
+            
+            // Move attention towards target
+            if let Some(target) = self.attention_targets.get(focus_idx) {
+                let dx = target.0 - self.attention_position.0;
+                let dy = target.1 - self.attention_position.1;
+                
+                self.attention_position.0 += dx * 0.1 * delta_time;
+                self.attention_position.1 += dy * 0.1 * delta_time;
+            }
+            
+            // Check if bored enough to switch focus
+            if self.boredom_level > 1.0 || self.focus_duration > self.params.max_focus_duration {
+                self.current_focus = None;
+                self.boredom_level = 0.0;
+                self.focus_duration = 0.0;
+            }
+        } else {
+            // Wandering state - looking for something interesting
+            let wander_force = (
+                rng.gen_range(-1.0..1.0) * 0.5,
+                rng.gen_range(-1.0..1.0) * 0.5,
+            );
+            
+            self.attention_position.0 += wander_force.0 * delta_time;
+            self.attention_position.1 += wander_force.1 * delta_time;
+            
+            // Check if close to any target
+            for (idx, target) in self.attention_targets.iter().enumerate() {
+                let distance = ((target.0 - self.attention_position.0).powi(2) 
+                    + (target.1 - self.attention_position.1).powi(2)).sqrt();
+                    
+                if distance < 0.3 && rng.gen::<f64>() < 0.5 * delta_time {
+                    self.current_focus = Some(idx);
+                    break;
+                }
+            }
+        }
+        
+        // Keep attention within bounds
+        self.attention_position.0 = self.attention_position.0.clamp(-2.0, 2.0);
+        self.attention_position.1 = self.attention_position.1.clamp(-2.0, 2.0);
+        
+        // Occasionally add new targets
+        if rng.gen::<f64>() < 0.01 * delta_time {
+            self.attention_targets.push((
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            ));
+            
+            // Remove old targets if too many
+            if self.attention_targets.len() > 8 {
+                self.attention_targets.remove(0);
+            }
+        }
+    }
+    
+    fn output_values(&self) -> Vec<f64> {
+        let focus_strength = if self.current_focus.is_some() {
+            1.0 - self.boredom_level
+        } else {
+            0.0
+        };
+
+        vec![
+            self.attention_position.0,
+            self.attention_position.1,
+            self.boredom_level,
+            focus_strength,
+        ]
+    }
+
+    fn snapshot(&self, timestamp: f64) -> RhythmData {
+        RhythmData {
+            rhythm_type: self.name().to_string(),
+            timestamp,
+            values: self.output_values(),
+            metadata: json!({
+                "focused": self.current_focus.is_some(),
+                "num_targets": self.attention_targets.len(),
+                "focus_duration": self.focus_duration,
+                "targets": self.attention_targets,
+                "curiosity": self.curiosity_map,
+                "seed": self.seed,
+            }),
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let state: Self = serde_json::from_value(state).map_err(|e| e.to_string())?;
+        state.params.check()?;
+        *self = state;
+        Ok(())
+    }
+
+    fn output_schema(&self) -> OutputSchema {
+        OutputSchema {
+            channels: vec![
+                ChannelSpec::new("x", "Attention position", "", -2.0, 2.0),
+                ChannelSpec::new("y", "Attention position", "", -2.0, 2.0),
+                ChannelSpec::new("boredom", "Boredom with the current state", "", 0.0, 1.0),
+                ChannelSpec::new("focus_strength", "Strength of focus on the current target", "", 0.0, 1.0),
+            ],
+            metadata: vec![
+                MetadataField::new("focused", "Whether attention is on a target", FieldType::Boolean, ""),
+                MetadataField::new("num_targets", "Number of attention targets", FieldType::Integer, ""),
+                MetadataField::new("focus_duration", "Time spent on the current target", FieldType::Number, "s"),
+                MetadataField::new("targets", "Positions of attention targets", FieldType::PointArray, ""),
+                MetadataField::new("curiosity", "Curiosity weight per stimulus kind", FieldType::Object, ""),
+                MetadataField::seed(),
+            ],
+        }
+    }
+
+    fn parameter_schema(&self) -> Vec<ParameterSpec> {
+        AttentionWanderingParams::schema()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.params.to_json()
+    }
+
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.params = self.params.merged(update)?;
+        Ok(self.params())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
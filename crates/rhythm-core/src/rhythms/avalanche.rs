@@ -0,0 +1,178 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+use super::power_law::{HistogramBin, LogHistogram, PowerLawFit};
+
+/// One completed avalanche.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Avalanche {
+    /// Simulated time the avalanche started, in seconds.
+    pub start_time: f64,
+    pub duration: f64,
+    /// Largest activity reached.
+    pub peak: f64,
+    /// Activity integrated over the avalanche.
+    pub size: f64,
+}
+
+/// Summary of every avalanche recorded so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvalancheStatistics {
+    /// Avalanches recorded since the log was cleared.
+    pub count: u64,
+    /// Power-law fit of the retained avalanche sizes.
+    pub size_fit: Option<PowerLawFit>,
+    /// Power-law fit of the retained avalanche durations.
+    pub duration_fit: Option<PowerLawFit>,
+    /// Mean activity one bin after an active bin, relative to that bin; 1 at
+    /// criticality, below 1 subcritical, above 1 supercritical.
+    pub branching_ratio: Option<f64>,
+    pub size_histogram: Vec<HistogramBin>,
+    pub duration_histogram: Vec<HistogramBin>,
+}
+
+/// Activity summed into fixed-width time bins, feeding a running estimate of
+/// the branching ratio `σ = Σ A(k+1) / Σ A(k)` over bins with `A(k) > 0`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct ActivityBins {
+    filled: f64,
+    current: f64,
+    previous: f64,
+    ancestors: f64,
+    descendants: f64,
+}
+
+impl ActivityBins {
+    fn observe(&mut self, activity: f64, delta_time: f64, width: f64) {
+        let mut remaining = delta_time;
+        while self.filled + remaining >= width {
+            let part = width - self.filled;
+            self.current += activity * part;
+            remaining -= part;
+            self.close();
+        }
+        self.current += activity * remaining;
+        self.filled += remaining;
+    }
+
+    fn close(&mut self) {
+        if self.previous > 0.0 {
+            self.ancestors += self.previous;
+            self.descendants += self.current;
+        }
+        self.previous = self.current;
+        self.current = 0.0;
+        self.filled = 0.0;
+    }
+
+    fn ratio(&self) -> Option<f64> {
+        (self.ancestors > 0.0).then(|| self.descendants / self.ancestors)
+    }
+}
+
+/// Log of avalanches with running size and duration histograms.
+///
+/// The histograms and branching ratio cover every avalanche since the log
+/// was cleared; the list itself, and the power-law fits made from it, keep
+/// only the most recent `CAPACITY`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AvalancheLog {
+    avalanches: VecDeque<Avalanche>,
+    count: u64,
+    sizes: LogHistogram,
+    durations: LogHistogram,
+    current: Option<Avalanche>,
+    activity: ActivityBins,
+}
+
+impl Default for AvalancheLog {
+    fn default() -> Self {
+        Self {
+            avalanches: VecDeque::new(),
+            count: 0,
+            sizes: LogHistogram::new(1e-3, 5),
+            durations: LogHistogram::new(1e-3, 5),
+            current: None,
+            activity: ActivityBins::default(),
+        }
+    }
+}
+
+impl AvalancheLog {
+    /// Most avalanches kept in the list.
+    pub const CAPACITY: usize = 10_000;
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Avalanches recorded since the log was cleared.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// The most recent `limit` avalanches, oldest first.
+    pub fn recent(&self, limit: usize) -> Vec<Avalanche> {
+        let skip = self.avalanches.len().saturating_sub(limit);
+        self.avalanches.iter().skip(skip).cloned().collect()
+    }
+
+    pub fn last(&self) -> Option<&Avalanche> {
+        self.avalanches.back()
+    }
+
+    pub fn begin(&mut self, start_time: f64) {
+        self.current = Some(Avalanche {
+            start_time,
+            duration: 0.0,
+            peak: 0.0,
+            size: 0.0,
+        });
+    }
+
+    /// Account one step of `activity`, which is zero outside avalanches;
+    /// `bin_width` is the time resolution of the branching ratio.
+    pub fn observe(&mut self, activity: f64, delta_time: f64, bin_width: f64) {
+        if let Some(avalanche) = &mut self.current {
+            avalanche.duration += delta_time;
+            avalanche.peak = avalanche.peak.max(activity);
+            avalanche.size += activity * delta_time;
+        }
+        self.activity.observe(activity, delta_time, bin_width);
+    }
+
+    pub fn end(&mut self) {
+        if let Some(avalanche) = self.current.take() {
+            self.sizes.add(avalanche.size);
+            self.durations.add(avalanche.duration);
+            self.count += 1;
+            self.avalanches.push_back(avalanche);
+            if self.avalanches.len() > Self::CAPACITY {
+                self.avalanches.pop_front();
+            }
+        }
+    }
+
+    pub fn branching_ratio(&self) -> Option<f64> {
+        self.activity.ratio()
+    }
+
+    /// Fit power laws to the retained avalanches and gather the histograms.
+    pub fn statistics(&self) -> AvalancheStatistics {
+        let sizes: Vec<f64> = self.avalanches.iter().map(|a| a.size).collect();
+        let durations: Vec<f64> = self.avalanches.iter().map(|a| a.duration).collect();
+        AvalancheStatistics {
+            count: self.count,
+            size_fit: PowerLawFit::fit(&sizes),
+            duration_fit: PowerLawFit::fit(&durations),
+            branching_ratio: self.branching_ratio(),
+            size_histogram: self.sizes.bins(),
+            duration_histogram: self.durations.bins(),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+use std::f64::consts::PI;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Generator for the coupling weights between oscillators.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Topology {
+    /// Every oscillator drives every other one with unit weight.
+    AllToAll,
+    /// Each oscillator is linked to `neighbors` oscillators on either side.
+    Ring { neighbors: usize },
+    /// Watts-Strogatz: a ring whose links are each rewired to a random
+    /// oscillator with probability `rewiring`.
+    SmallWorld { neighbors: usize, rewiring: f64 },
+    /// Barabási-Albert: oscillators join one at a time, linking to
+    /// `attachments` existing ones with probability proportional to degree.
+    ScaleFree { attachments: usize },
+    /// Weights fall off as `exp(-|ln(f_i / f_j)| / scale)` with the distance
+    /// between time scales. Positive `asymmetry` strengthens the drive from
+    /// slower to faster oscillators, negative from faster to slower.
+    Hierarchical { scale: f64, asymmetry: f64 },
+}
+
+impl Topology {
+    /// Unit-free weight matrix for oscillators with the given frequencies;
+    /// `weights[i][j]` is how strongly oscillator `j` drives oscillator `i`.
+    pub fn weights(&self, frequencies: &[f64], rng: &mut impl Rng) -> Result<Vec<Vec<f64>>, String> {
+        let n = frequencies.len();
+        let mut weights = vec![vec![0.0; n]; n];
+
+        match *self {
+            Topology::AllToAll => weights = all_to_all(n),
+            Topology::Ring { neighbors } => {
+                check_neighbors(neighbors)?;
+                for (i, j) in ring_links(n, neighbors) {
+                    link(&mut weights, i, j);
+                }
+            }
+            Topology::SmallWorld { neighbors, rewiring } => {
+                check_neighbors(neighbors)?;
+                if !(0.0..=1.0).contains(&rewiring) {
+                    return Err(format!("rewiring must be in [0, 1], got {}", rewiring));
+                }
+                for (i, j) in ring_links(n, neighbors) {
+                    link(&mut weights, i, j);
+                }
+                for (i, j) in ring_links(n, neighbors) {
+                    if !rng.gen_bool(rewiring) {
+                        continue;
+                    }
+                    let free: Vec<usize> = (0..n).filter(|&k| k != i && weights[i][k] == 0.0).collect();
+                    if free.is_empty() {
+                        continue;
+                    }
+                    let k = free[rng.gen_range(0..free.len())];
+                    unlink(&mut weights, i, j);
+                    link(&mut weights, i, k);
+                }
+            }
+            Topology::ScaleFree { attachments } => {
+                if attachments == 0 {
+                    return Err("attachments must be at least 1".to_string());
+                }
+                // Fully connected seed graph, then preferential attachment
+                let seed_size = (attachments + 1).min(n);
+                for i in 0..seed_size {
+                    for j in (i + 1)..seed_size {
+                        link(&mut weights, i, j);
+                    }
+                }
+                for new in seed_size..n {
+                    let mut degrees: Vec<f64> = weights[..new]
+                        .iter()
+                        .map(|row| row.iter().filter(|&&w| w != 0.0).count() as f64)
+                        .collect();
+                    for _ in 0..attachments.min(new) {
+                        let total: f64 = degrees.iter().sum();
+                        let target = if total > 0.0 {
+                            let mut pick = rng.gen_range(0.0..total);
+                            degrees
+                                .iter()
+                                .position(|&degree| {
+                                    pick -= degree;
+                                    pick < 0.0
+                                })
+                                .unwrap_or(new - 1)
+                        } else {
+                            rng.gen_range(0..new)
+                        };
+                        link(&mut weights, new, target);
+                        // Never pick the same oscillator twice
+                        degrees[target] = 0.0;
+                    }
+                }
+            }
+            Topology::Hierarchical { scale, asymmetry } => {
+                if !(scale > 0.0 && scale.is_finite()) {
+                    return Err(format!("scale must be positive, got {}", scale));
+                }
+                if !(-1.0..=1.0).contains(&asymmetry) {
+                    return Err(format!("asymmetry must be in [-1, 1], got {}", asymmetry));
+                }
+                for (i, row) in weights.iter_mut().enumerate() {
+                    for (j, weight) in row.iter_mut().enumerate() {
+                        if i == j {
+                            continue;
+                        }
+                        let distance = (frequencies[i] / frequencies[j]).ln();
+                        let direction = if distance > 0.0 {
+                            // j is the slower oscillator
+                            1.0 + asymmetry
+                        } else if distance < 0.0 {
+                            1.0 - asymmetry
+                        } else {
+                            1.0
+                        };
+                        *weight = direction * (-distance.abs() / scale).exp();
+                    }
+                }
+            }
+        }
+
+        Ok(weights)
+    }
+}
+
+fn all_to_all(n: usize) -> Vec<Vec<f64>> {
+    off_diagonal(n, 1.0)
+}
+
+fn check_neighbors(neighbors: usize) -> Result<(), String> {
+    if neighbors == 0 {
+        return Err("neighbors must be at least 1".to_string());
+    }
+    Ok(())
+}
+
+/// Links of a ring lattice, each listed once.
+fn ring_links(n: usize, neighbors: usize) -> Vec<(usize, usize)> {
+    let reach = neighbors.min(n / 2);
+    let mut links = Vec::new();
+    for i in 0..n {
+        for d in 1..=reach {
+            let j = (i + d) % n;
+            // With an even count the opposite oscillator is reached from both sides
+            if 2 * d == n && j < i {
+                continue;
+            }
+            links.push((i, j));
+        }
+    }
+    links
+}
+
+fn link(weights: &mut [Vec<f64>], i: usize, j: usize) {
+    weights[i][j] = 1.0;
+    weights[j][i] = 1.0;
+}
+
+fn unlink(weights: &mut [Vec<f64>], i: usize, j: usize) {
+    weights[i][j] = 0.0;
+    weights[j][i] = 0.0;
+}
+
+/// Directed, weighted coupling between oscillators with Sakaguchi-Kuramoto
+/// phase lags and transmission delays: oscillator `i` is pulled by
+/// `weights[i][j] * sin(θ_j(t - delays[i][j]) - θ_i(t) - lags[i][j])`, scaled
+/// by the rhythm's `coupling_strength`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct CouplingMatrix {
+    pub weights: Vec<Vec<f64>>,
+    /// Phase lags, in rad.
+    pub lags: Vec<Vec<f64>>,
+    /// Generator the weights came from, or `None` if they were set directly.
+    pub topology: Option<Topology>,
+    /// Phase lag applied between every pair by the generator, in rad.
+    pub phase_lag: f64,
+    /// Transmission delays, in s.
+    #[serde(default)]
+    pub delays: Vec<Vec<f64>>,
+    /// Delay applied between every pair by the generator, in s.
+    #[serde(default)]
+    pub delay: f64,
+}
+
+impl CouplingMatrix {
+    pub const MAX_WEIGHT: f64 = 10.0;
+    /// Longest transmission delay, which bounds the phase history kept.
+    pub const MAX_DELAY: f64 = 10.0;
+
+    /// Matrix for `weights` and optional `lags` and `delays`, which default to zero.
+    pub fn new(
+        weights: Vec<Vec<f64>>,
+        lags: Option<Vec<Vec<f64>>>,
+        delays: Option<Vec<Vec<f64>>>,
+    ) -> Result<Self, String> {
+        let n = weights.len();
+        let matrix = Self {
+            lags: lags.unwrap_or_else(|| vec![vec![0.0; n]; n]),
+            delays: delays.unwrap_or_else(|| vec![vec![0.0; n]; n]),
+            weights,
+            topology: None,
+            phase_lag: 0.0,
+            delay: 0.0,
+        };
+        matrix.validate(n)?;
+        Ok(matrix)
+    }
+
+    /// Unit all-to-all coupling without lags, the classic Kuramoto model.
+    pub fn all_to_all(n: usize) -> Self {
+        Self {
+            weights: all_to_all(n),
+            lags: vec![vec![0.0; n]; n],
+            topology: Some(Topology::AllToAll),
+            phase_lag: 0.0,
+            delays: vec![vec![0.0; n]; n],
+            delay: 0.0,
+        }
+    }
+
+    pub fn generate(
+        topology: Topology,
+        phase_lag: f64,
+        delay: f64,
+        frequencies: &[f64],
+        rng: &mut impl Rng,
+    ) -> Result<Self, String> {
+        check_lag(phase_lag)?;
+        check_delay(delay)?;
+        let n = frequencies.len();
+
+        Ok(Self {
+            weights: topology.weights(frequencies, rng)?,
+            lags: off_diagonal(n, phase_lag),
+            topology: Some(topology),
+            phase_lag,
+            delays: off_diagonal(n, delay),
+            delay,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Longest delay over all pairs, in s.
+    pub fn max_delay(&self) -> f64 {
+        self.delays.iter().flatten().fold(0.0, |max, &delay| f64::max(max, delay))
+    }
+
+    /// Check the matrix is square, matches `n` oscillators and holds valid values.
+    pub fn validate(&self, n: usize) -> Result<(), String> {
+        for (name, matrix) in [("weights", &self.weights), ("lags", &self.lags), ("delays", &self.delays)] {
+            if matrix.len() != n || matrix.iter().any(|row| row.len() != n) {
+                return Err(format!("{} must be a {}x{} matrix", name, n, n));
+            }
+        }
+        for row in &self.weights {
+            for &weight in row {
+                if !(-Self::MAX_WEIGHT..=Self::MAX_WEIGHT).contains(&weight) {
+                    return Err(format!(
+                        "Weights must be in [{}, {}], got {}",
+                        -Self::MAX_WEIGHT,
+                        Self::MAX_WEIGHT,
+                        weight
+                    ));
+                }
+            }
+        }
+        for row in &self.lags {
+            for &lag in row {
+                check_lag(lag)?;
+            }
+        }
+        for row in &self.delays {
+            for &delay in row {
+                check_delay(delay)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop oscillator `index` from a matrix that was set directly.
+    pub fn remove(&mut self, index: usize) {
+        for matrix in [&mut self.weights, &mut self.lags, &mut self.delays] {
+            matrix.remove(index);
+            for row in matrix.iter_mut() {
+                row.remove(index);
+            }
+        }
+    }
+
+    /// Append an oscillator to a matrix that was set directly, coupled to and
+    /// from every other oscillator with unit weight, no lag and no delay.
+    pub fn push(&mut self) {
+        let n = self.size();
+        for row in &mut self.weights {
+            row.push(1.0);
+        }
+        let mut row = vec![1.0; n + 1];
+        row[n] = 0.0;
+        self.weights.push(row);
+
+        for matrix in [&mut self.lags, &mut self.delays] {
+            for row in matrix.iter_mut() {
+                row.push(0.0);
+            }
+            matrix.push(vec![0.0; n + 1]);
+        }
+    }
+}
+
+/// `n`x`n` matrix holding `value` everywhere but on the diagonal.
+fn off_diagonal(n: usize, value: f64) -> Vec<Vec<f64>> {
+    (0..n)
+        .map(|i| (0..n).map(|j| if i == j { 0.0 } else { value }).collect())
+        .collect()
+}
+
+fn check_delay(delay: f64) -> Result<(), String> {
+    if !(0.0..=CouplingMatrix::MAX_DELAY).contains(&delay) {
+        return Err(format!(
+            "Delays must be in [0, {}] s, got {}",
+            CouplingMatrix::MAX_DELAY,
+            delay
+        ));
+    }
+    Ok(())
+}
+
+fn check_lag(lag: f64) -> Result<(), String> {
+    if !(-PI..=PI).contains(&lag) {
+        return Err(format!("Phase lags must be in [-π, π] rad, got {}", lag));
+    }
+    Ok(())
+}
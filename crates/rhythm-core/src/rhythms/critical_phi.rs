@@ -0,0 +1,527 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{ChannelSpec, FieldType, MetadataField, OutputSchema, ParameterSpec, Rhythm, RhythmParams};
+use super::avalanche::{AvalancheLog, AvalancheStatistics};
+use super::criticality::{ActivityNetwork, CriticalityEngine};
+use super::integrated_information::{GaussianSystem, IntegratedInformation};
+use super::integration_matrix::{dense_links, MatrixConfig, Plasticity};
+use crate::{ElapsedTime, Integrator, RhythmData};
+use crate::rng::{random_seed, rng_from_seed, RhythmRng};
+use rand::Rng;
+use std::any::Any;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CriticalPhiParams {
+    /// Critical point phi relaxes towards.
+    pub phi_target: f64,
+    /// Rate at which phi is pulled back to the target, in 1/s.
+    pub relaxation_rate: f64,
+    /// Noise amplitude around the critical point.
+    pub fluctuation: f64,
+    /// Phi level that triggers an avalanche.
+    pub avalanche_threshold: f64,
+    pub min_avalanche_duration: f64,
+    pub max_avalanche_duration: f64,
+    /// Gain of the linear Gaussian system the integration matrix defines for
+    /// the Φ estimate; closer to 1 means longer memory.
+    pub system_gain: f64,
+    /// Width of the time bins whose activity the branching ratio compares,
+    /// under the threshold engine. The network engines compare successive
+    /// generations.
+    pub branching_bin: f64,
+    /// Duration of one generation of the network engines.
+    pub generation_time: f64,
+    /// Spontaneous activations (branching) or grains added (sandpile) per
+    /// second while the network is quiet.
+    pub drive_rate: f64,
+    /// Step of the branching gain towards a branching ratio of 1, per generation.
+    pub tuning_rate: f64,
+    /// Probability that a toppled sandpile grain is lost.
+    pub dissipation: f64,
+}
+
+impl Default for CriticalPhiParams {
+    fn default() -> Self {
+        Self {
+            phi_target: 0.7,
+            relaxation_rate: 0.1,
+            fluctuation: 0.1,
+            avalanche_threshold: 0.85,
+            min_avalanche_duration: 0.5,
+            max_avalanche_duration: 2.0,
+            system_gain: 0.9,
+            branching_bin: 0.1,
+            generation_time: 0.01,
+            drive_rate: 1.0,
+            tuning_rate: 0.01,
+            dissipation: 0.05,
+        }
+    }
+}
+
+impl RhythmParams for CriticalPhiParams {
+    fn schema() -> Vec<ParameterSpec> {
+        let defaults = Self::default();
+        vec![
+            ParameterSpec::new("phi_target", "Critical point phi relaxes towards", defaults.phi_target, 0.0, 1.5, ""),
+            ParameterSpec::new("relaxation_rate", "Pull of phi back towards the target", defaults.relaxation_rate, 0.0, 10.0, "1/s"),
+            ParameterSpec::new("fluctuation", "Noise amplitude around the critical point", defaults.fluctuation, 0.0, 1.0, "1/s"),
+            ParameterSpec::new("avalanche_threshold", "Phi level that triggers an avalanche", defaults.avalanche_threshold, 0.0, 1.5, ""),
+            ParameterSpec::new("min_avalanche_duration", "Shortest avalanche", defaults.min_avalanche_duration, 0.01, 60.0, "s"),
+            ParameterSpec::new("max_avalanche_duration", "Longest avalanche", defaults.max_avalanche_duration, 0.01, 60.0, "s"),
+            ParameterSpec::new("system_gain", "Gain of the Gaussian system used to estimate integrated information", defaults.system_gain, 0.0, 0.99, ""),
+            ParameterSpec::new("branching_bin", "Time bin over which the branching ratio compares successive activity; threshold engine only, the network engines compare generations", defaults.branching_bin, 0.001, 10.0, "s"),
+            ParameterSpec::new("generation_time", "Duration of one generation of the branching and sandpile engines", defaults.generation_time, 0.001, 1.0, "s"),
+            ParameterSpec::new("drive_rate", "Spontaneous activations or sandpile grains added per second", defaults.drive_rate, 0.0, 100.0, "1/s"),
+            ParameterSpec::new("tuning_rate", "Adaptation of the branching gain towards a branching ratio of 1", defaults.tuning_rate, 0.0, 1.0, ""),
+            ParameterSpec::new("dissipation", "Probability that a toppled sandpile grain is lost", defaults.dissipation, 0.001, 1.0, ""),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.min_avalanche_duration > self.max_avalanche_duration {
+            return Err("min_avalanche_duration must not exceed max_avalanche_duration".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct CriticalPhi {
+    phi_value: f64,
+    params: CriticalPhiParams,
+    avalanche_active: bool,
+    avalanche_duration: f64,
+    integration_matrix: Vec<Vec<f64>>,
+    /// Which entries of the integration matrix are links.
+    #[serde(default)]
+    links: Vec<Vec<bool>>,
+    #[serde(default)]
+    matrix_config: MatrixConfig,
+    #[serde(default)]
+    engine: CriticalityEngine,
+    /// Network state of the branching and sandpile engines.
+    #[serde(default)]
+    network: ActivityNetwork,
+    /// Time not yet spent on whole generations.
+    #[serde(default)]
+    pending_time: f64,
+    #[serde(default)]
+    avalanches: AvalancheLog,
+    #[serde(default)]
+    time: ElapsedTime,
+    /// Latest Φ estimate of the integration matrix. Saved with the state so
+    /// a resumed run keeps the same estimate schedule.
+    #[serde(default)]
+    information: Option<IntegratedInformation>,
+    /// Simulated time since `information` was estimated.
+    #[serde(default)]
+    information_age: f64,
+    seed: u64,
+    rng: RhythmRng,
+}
+
+impl Default for CriticalPhi {
+    fn default() -> Self {
+        Self::from_seed(random_seed())
+    }
+}
+
+impl CriticalPhi {
+    /// Create the rhythm with all randomness drawn from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        let matrix_config = MatrixConfig::default();
+        let mut rng = rng_from_seed(seed);
+        let (matrix, links) = matrix_config.generate(&mut rng);
+        let size = matrix.len();
+
+        let mut rhythm = Self {
+            phi_value: 0.5,
+            params: CriticalPhiParams::default(),
+            avalanche_active: false,
+            avalanche_duration: 0.0,
+            integration_matrix: matrix,
+            links,
+            matrix_config,
+            engine: CriticalityEngine::default(),
+            network: ActivityNetwork::new(size),
+            pending_time: 0.0,
+            avalanches: AvalancheLog::default(),
+            time: ElapsedTime::default(),
+            information: None,
+            information_age: 0.0,
+            seed,
+            rng,
+        };
+        rhythm.estimate_information();
+        rhythm
+    }
+
+    /// Simulated seconds between Φ estimates while plasticity reshapes the
+    /// integration matrix. The estimate searches every bipartition, so it is
+    /// not redone on every frame.
+    pub const INFORMATION_INTERVAL: f64 = 0.1;
+
+    /// Integrated information of the integration matrix read as a linear
+    /// Gaussian system, or `None` if the matrix is too large to search.
+    /// Under plasticity it lags the matrix by at most `INFORMATION_INTERVAL`.
+    pub fn integrated_information(&self) -> Option<&IntegratedInformation> {
+        self.information.as_ref()
+    }
+
+    fn estimate_information(&mut self) {
+        self.information = GaussianSystem::from_weights(&self.integration_matrix, self.params.system_gain)
+            .ok()
+            .and_then(|system| system.integrated_information());
+        self.information_age = 0.0;
+    }
+
+    /// Weights of the integration matrix; `[i][j]` is the influence of node
+    /// `j` on node `i`.
+    pub fn integration_matrix(&self) -> &[Vec<f64>] {
+        &self.integration_matrix
+    }
+
+    pub fn matrix_config(&self) -> &MatrixConfig {
+        &self.matrix_config
+    }
+
+    /// Draw a new integration matrix from `config`. The network restarts
+    /// quiet and the avalanche log starts over.
+    pub fn configure_matrix(&mut self, config: MatrixConfig) -> Result<(), String> {
+        config.validate()?;
+        let (matrix, links) = config.generate(&mut self.rng);
+        self.integration_matrix = matrix;
+        self.links = links;
+        self.matrix_config = config;
+        self.estimate_information();
+        self.set_engine(self.engine);
+        Ok(())
+    }
+
+    pub fn engine(&self) -> CriticalityEngine {
+        self.engine
+    }
+
+    /// Switch avalanche mechanism. The network restarts quiet and the
+    /// avalanche log starts over, so its statistics describe one engine.
+    pub fn set_engine(&mut self, engine: CriticalityEngine) {
+        self.engine = engine;
+        self.avalanches.clear();
+        self.avalanche_active = false;
+        self.avalanche_duration = 0.0;
+        self.phi_value = self.params.phi_target;
+        self.network.reset(self.integration_matrix.len());
+        self.pending_time = 0.0;
+    }
+
+    /// Activity of each network node in the latest generation; all zero
+    /// under the threshold engine.
+    pub fn node_activity(&self) -> &[f64] {
+        self.network.activity()
+    }
+
+    /// Check state read back from a snapshot before it replaces the current one.
+    fn validate(&self) -> Result<(), String> {
+        self.params.check()?;
+        self.matrix_config.validate()?;
+        let n = self.matrix_config.size;
+        if self.integration_matrix.len() != n || self.integration_matrix.iter().any(|row| row.len() != n) {
+            return Err(format!("integration_matrix must be a {}x{} matrix", n, n));
+        }
+        if self.links.len() != n || self.links.iter().any(|row| row.len() != n) {
+            return Err(format!("links must be a {}x{} matrix", n, n));
+        }
+        if self.integration_matrix.iter().flatten().any(|weight| !(0.0..=1.0).contains(weight)) {
+            return Err("Integration weights must be in [0, 1]".to_string());
+        }
+        if !(self.information_age >= 0.0 && self.information_age.is_finite()) {
+            return Err(format!("information_age must not be negative, got {}", self.information_age));
+        }
+        self.network.validate(n)
+    }
+
+    /// Phi fluctuates around its target and avalanches are random walks
+    /// triggered by crossing the threshold.
+    fn update_threshold(&mut self, delta_time: f64) {
+        let rng = &mut self.rng;
+
+        if self.avalanche_active {
+            // During avalanche, phi spikes dramatically
+            self.phi_value += rng.gen_range(-0.5..0.5) * delta_time * 10.0;
+            self.phi_value = self.phi_value.clamp(0.0, 1.5);
+            self.avalanche_duration -= delta_time;
+
+            // Activity is the excursion of phi from the critical point
+            let activity = (self.phi_value - self.params.phi_target).abs();
+            self.avalanches.observe(activity, delta_time, self.params.branching_bin);
+
+            if self.avalanche_duration <= 0.0 {
+                self.avalanche_active = false;
+                self.phi_value = self.params.phi_target;
+                self.avalanches.end();
+            }
+        } else {
+            // Normal fluctuations around critical point
+            let drift = (self.params.phi_target - self.phi_value) * self.params.relaxation_rate;
+            let noise = rng.gen_range(-1.0..1.0) * self.params.fluctuation;
+            
+            self.phi_value += (drift + noise) * delta_time;
+            self.avalanches.observe(0.0, delta_time, self.params.branching_bin);
+
+            // Check for avalanche trigger
+            if self.phi_value > self.params.avalanche_threshold {
+                self.avalanche_active = true;
+                self.avalanche_duration = rng.gen_range(self.params.min_avalanche_duration..=self.params.max_avalanche_duration);
+                self.avalanches.begin(self.time.as_secs_f64() + delta_time);
+            }
+        }
+    }
+
+    /// Run the network engine for every whole generation in `delta_time`, up
+    /// to the same `Integrator::MAX_SUBSTEPS` cap as the continuous rhythms,
+    /// with excess time dropped the same way. An avalanche lasts while any
+    /// node is active; phi sits above its target by the fraction of active
+    /// nodes.
+    ///
+    /// `generate` runs one generation of the engine, given the drive of that
+    /// generation.
+    fn update_network(
+        &mut self,
+        delta_time: f64,
+        generate: impl Fn(&mut ActivityNetwork, &[Vec<f64>], &CriticalPhiParams, f64, &mut RhythmRng),
+    ) {
+        let generation = self.params.generation_time;
+        let size = self.integration_matrix.len();
+        if self.network.size() != size {
+            self.network.reset(size);
+        }
+
+        self.pending_time += delta_time;
+        let mut elapsed = self.time.as_secs_f64();
+        let mut generations = 0;
+        while self.pending_time >= generation {
+            if generations == Integrator::MAX_SUBSTEPS {
+                self.pending_time = 0.0;
+                break;
+            }
+            generations += 1;
+            self.pending_time -= generation;
+            elapsed += generation;
+
+            let drive = (self.params.drive_rate * generation).min(1.0);
+            generate(&mut self.network, &self.integration_matrix, &self.params, drive, &mut self.rng);
+
+            let active = self.network.active();
+            if active > 0.0 && !self.avalanches.is_active() {
+                self.avalanches.begin(elapsed - generation);
+            }
+            self.avalanches.observe(active, generation, generation);
+            if active == 0.0 {
+                self.avalanches.end();
+            }
+        }
+
+        let active = self.network.active();
+        self.avalanche_active = active > 0.0;
+        self.phi_value = self.params.phi_target + active / size.max(1) as f64;
+    }
+
+    /// Log of the avalanches since the rhythm was created, reseeded or the
+    /// log was cleared.
+    pub fn avalanches(&self) -> &AvalancheLog {
+        &self.avalanches
+    }
+
+    /// Power-law fits, histograms and branching ratio of the logged
+    /// avalanches.
+    pub fn avalanche_statistics(&self) -> AvalancheStatistics {
+        self.avalanches.statistics()
+    }
+
+    pub fn clear_avalanches(&mut self) {
+        self.avalanches.clear();
+    }
+}
+
+impl Rhythm for CriticalPhi {
+    fn name(&self) -> &'static str {
+        "critical_phi"
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        let params = self.params.clone();
+        let engine = self.engine;
+        let matrix_config = self.matrix_config.clone();
+        *self = Self::from_seed(seed);
+        self.params = params;
+        self.engine = engine;
+        if matrix_config != self.matrix_config {
+            let (matrix, links) = matrix_config.generate(&mut self.rng);
+            self.integration_matrix = matrix;
+            self.links = links;
+            self.matrix_config = matrix_config;
+            self.network.reset(self.integration_matrix.len());
+        }
+        self.estimate_information();
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        match self.engine {
+            CriticalityEngine::Threshold => self.update_threshold(delta_time),
+            CriticalityEngine::Branching => self.update_network(delta_time, |network, weights, params, drive, rng| {
+                network.branch(weights, drive / weights.len().max(1) as f64, params.tuning_rate, rng)
+            }),
+            CriticalityEngine::Sandpile => self.update_network(delta_time, |network, weights, params, drive, rng| {
+                network.topple(weights, params.dissipation, drive, rng)
+            }),
+        }
+
+        // Keep phi in reasonable bounds
+        self.phi_value = self.phi_value.clamp(0.0, 1.5);
+        
+        // Evolve the integration matrix; under the threshold engine every
+        // node counts as active
+        let activity = match self.engine {
+            CriticalityEngine::Threshold => vec![1.0; self.integration_matrix.len()],
+            CriticalityEngine::Branching | CriticalityEngine::Sandpile => self.network.activity().to_vec(),
+        };
+        let plasticity = self.matrix_config.plasticity;
+        for (i, (row, links)) in self.integration_matrix.iter_mut().zip(&self.links).enumerate() {
+            for (j, (weight, &linked)) in row.iter_mut().zip(links).enumerate() {
+                if linked {
+                    let rate = plasticity.rate(*weight, activity[i] * activity[j], self.phi_value, self.params.phi_target, &mut self.rng);
+                    *weight = (*weight + rate * delta_time).clamp(0.0, 1.0);
+                }
+            }
+        }
+        if plasticity != Plasticity::Frozen {
+            self.information_age += delta_time;
+            if self.information_age >= Self::INFORMATION_INTERVAL {
+                self.estimate_information();
+            }
+        }
+
+        self.time.advance(delta_time);
+    }
+
+    fn output_values(&self) -> Vec<f64> {
+        let gaussian_phi = self.information.as_ref().map_or(0.0, |information| information.phi);
+        [self.phi_value, gaussian_phi].iter().chain(self.network.activity()).copied().collect()
+    }
+
+    fn snapshot(&self, timestamp: f64) -> RhythmData {
+        let matrix_sum: f64 = self.integration_matrix
+            .iter()
+            .flatten()
+            .sum();
+            
+        let information = self.integrated_information();
+
+        RhythmData {
+            rhythm_type: self.name().to_string(),
+            timestamp,
+            values: self.output_values(),
+            metadata: json!({
+                "integrated_information": information,
+                "avalanche_active": self.avalanche_active,
+                "criticality_engine": self.engine,
+                "branching_gain": self.network.gain(),
+                "avalanche_count": self.avalanches.count(),
+                "last_avalanche": self.avalanches.last(),
+                "branching_ratio": self.avalanches.branching_ratio(),
+                "matrix_complexity": matrix_sum,
+                "integration_matrix": self.integration_matrix,
+                "criticality": (self.phi_value - self.params.phi_target).abs(),
+                "seed": self.seed,
+            }),
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let mut state: Self = serde_json::from_value(state).map_err(|e| e.to_string())?;
+        // Snapshots from before links were tracked have a dense matrix
+        if state.links.is_empty() {
+            state.links = dense_links(state.integration_matrix.len());
+        }
+        // Snapshots from before the network engines have no network
+        if state.network.size() == 0 {
+            state.network.reset(state.integration_matrix.len());
+        }
+        state.validate()?;
+        // Snapshots from before the estimate was saved; an estimate that is
+        // legitimately absent comes out absent again
+        if state.information.is_none() {
+            let age = state.information_age;
+            state.estimate_information();
+            state.information_age = age;
+        }
+        *self = state;
+        Ok(())
+    }
+
+    fn output_schema(&self) -> OutputSchema {
+        let mut channels = vec![
+            ChannelSpec::new("phi", "Heuristic integrated information level", "", 0.0, 1.5),
+            ChannelSpec::new(
+                "gaussian_phi",
+                &format!(
+                    "Integrated information of the integration matrix across its minimum information partition; \
+                     0 when the matrix has more than {} nodes and is not searched, in which case integrated_information is null",
+                    GaussianSystem::MAX_NODES
+                ),
+                "nats",
+                0.0,
+                1.0,
+            ),
+        ];
+        channels.extend((0..self.network.size()).map(|i| {
+            ChannelSpec::new(&format!("node_{}", i), &format!("Activity of network node {}; zero under the threshold engine", i), "", 0.0, 1.0)
+        }));
+
+        OutputSchema {
+            channels,
+            metadata: vec![
+                MetadataField::new("integrated_information", "phi, whole_minus_sum, mutual_information and partition of the Gaussian estimate, or null if it is not computed", FieldType::Object, "nats"),
+                MetadataField::new("avalanche_active", "Whether an avalanche is in progress", FieldType::Boolean, ""),
+                MetadataField::new("criticality_engine", "threshold, branching or sandpile", FieldType::String, ""),
+                MetadataField::new("branching_gain", "Self-tuned gain of the branching engine", FieldType::Number, ""),
+                MetadataField::new("avalanche_count", "Avalanches logged since the log was cleared", FieldType::Number, ""),
+                MetadataField::new("last_avalanche", "start_time, duration, peak and size of the latest completed avalanche, or null", FieldType::Object, ""),
+                MetadataField::new("branching_ratio", "Activity in one bin relative to the active bin before it, or null before any avalanche", FieldType::Number, ""),
+                MetadataField::new("matrix_complexity", "Sum of integration matrix weights", FieldType::Number, ""),
+                MetadataField::new("integration_matrix", "Weights of the integration matrix; row i holds the influence of every node on node i", FieldType::Matrix, ""),
+                MetadataField::new("criticality", "Distance of phi from its target", FieldType::Number, ""),
+                MetadataField::seed(),
+            ],
+        }
+    }
+
+    fn parameter_schema(&self) -> Vec<ParameterSpec> {
+        CriticalPhiParams::schema()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.params.to_json()
+    }
+
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.params = self.params.merged(update)?;
+        // The estimate depends on system_gain
+        self.estimate_information();
+        Ok(self.params())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
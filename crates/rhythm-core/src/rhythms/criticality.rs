@@ -0,0 +1,179 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::RhythmRng;
+
+/// Mechanism that produces avalanches in the critical-phi rhythm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CriticalityEngine {
+    /// Phi itself fluctuates around its target and an avalanche starts
+    /// whenever it crosses `avalanche_threshold`.
+    Threshold,
+    /// The integration matrix carries a stochastic branching process whose
+    /// gain adapts until each activation causes one more on average.
+    Branching,
+    /// The integration matrix carries a Bak-Tang-Wiesenfeld sandpile with
+    /// random dissipation, which organizes itself into a critical state.
+    Sandpile,
+}
+
+impl Default for CriticalityEngine {
+    fn default() -> Self {
+        CriticalityEngine::Threshold
+    }
+}
+
+/// Node states of a branching process or sandpile running on a weighted
+/// network, advanced one generation at a time.
+///
+/// `weights[i][j]` is the link from node `j` to node `i`, matching the
+/// integration matrix.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivityNetwork {
+    /// Activity of each node in the latest generation, 0 or 1.
+    activity: Vec<f64>,
+    /// Sandpile grains held by each node.
+    grains: Vec<u32>,
+    /// Branching gain; the expected number of activations each active node
+    /// causes on a network of average out-weight.
+    gain: f64,
+}
+
+impl Default for ActivityNetwork {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl ActivityNetwork {
+    /// Largest gain the self-organized tuning may reach.
+    pub const MAX_GAIN: f64 = 4.0;
+
+    pub fn new(size: usize) -> Self {
+        Self {
+            activity: vec![0.0; size],
+            grains: vec![0; size],
+            gain: 1.0,
+        }
+    }
+
+    pub fn size(&self) -> usize {
+        self.activity.len()
+    }
+
+    /// Activity of each node in the latest generation.
+    pub fn activity(&self) -> &[f64] {
+        &self.activity
+    }
+
+    /// Number of nodes active in the latest generation.
+    pub fn active(&self) -> f64 {
+        self.activity.iter().sum()
+    }
+
+    pub fn gain(&self) -> f64 {
+        self.gain
+    }
+
+    /// Check a network read back from saved state has `size` nodes.
+    pub fn validate(&self, size: usize) -> Result<(), String> {
+        if self.activity.len() != size || self.grains.len() != size {
+            return Err(format!("Network must have {} nodes", size));
+        }
+        if !(0.0..=Self::MAX_GAIN).contains(&self.gain) {
+            return Err(format!("Network gain must be in [0, {}], got {}", Self::MAX_GAIN, self.gain));
+        }
+        Ok(())
+    }
+
+    /// Quiesce every node, keeping the tuned gain.
+    pub fn reset(&mut self, size: usize) {
+        let gain = self.gain;
+        *self = Self::new(size);
+        self.gain = gain;
+    }
+
+    /// One generation of the branching process. Each active node `j`
+    /// activates node `i` with probability `gain · weights[i][j]` relative to
+    /// the mean out-weight, and idle nodes fire spontaneously with
+    /// probability `drive`. The gain then moves by `tuning` times the
+    /// shortfall of descendants per ancestor from 1.
+    pub fn branch(&mut self, weights: &[Vec<f64>], drive: f64, tuning: f64, rng: &mut RhythmRng) {
+        let n = self.size();
+        let mean_out_weight = weights.iter().flatten().map(|w| w.abs()).sum::<f64>() / n.max(1) as f64;
+        let ancestors = self.active();
+
+        let mut next = vec![0.0; n];
+        let mut descendants = 0.0;
+        for (i, row) in weights.iter().enumerate() {
+            let activated = (0..n).any(|j| {
+                self.activity[j] > 0.0
+                    && mean_out_weight > 0.0
+                    && rng.gen_bool((self.gain * row[j].abs() / mean_out_weight).min(1.0))
+            });
+            if activated {
+                next[i] = 1.0;
+                descendants += 1.0;
+            } else if rng.gen_bool(drive) {
+                next[i] = 1.0;
+            }
+        }
+        self.activity = next;
+
+        if ancestors > 0.0 {
+            self.gain = (self.gain + tuning * (1.0 - descendants / ancestors)).clamp(0.0, Self::MAX_GAIN);
+        }
+    }
+
+    /// One generation of the sandpile. Every node holding at least as many
+    /// grains as it has outgoing links topples, sending one grain per link
+    /// to neighbours drawn in proportion to link weight; each grain is lost
+    /// with probability `dissipation`, as is everything a node without
+    /// links topples. When nothing topples a grain lands on a random node
+    /// with probability `drive`.
+    pub fn topple(&mut self, weights: &[Vec<f64>], dissipation: f64, drive: f64, rng: &mut RhythmRng) {
+        let n = self.size();
+        let out_links: Vec<Vec<(usize, f64)>> = (0..n)
+            .map(|j| {
+                (0..n)
+                    .filter(|&i| i != j && weights[i][j] != 0.0)
+                    .map(|i| (i, weights[i][j].abs()))
+                    .collect()
+            })
+            .collect();
+        let thresholds: Vec<u32> = out_links.iter().map(|links| links.len().max(1) as u32).collect();
+
+        let unstable: Vec<usize> = (0..n).filter(|&j| self.grains[j] >= thresholds[j]).collect();
+        self.activity = vec![0.0; n];
+
+        if unstable.is_empty() {
+            if n > 0 && rng.gen_bool(drive) {
+                let node = rng.gen_range(0..n);
+                self.grains[node] += 1;
+            }
+            return;
+        }
+
+        for j in unstable {
+            self.activity[j] = 1.0;
+            self.grains[j] -= thresholds[j];
+            let links = &out_links[j];
+            let total: f64 = links.iter().map(|(_, w)| w).sum();
+            for _ in 0..links.len() {
+                if rng.gen_bool(dissipation) {
+                    continue;
+                }
+                let mut pick = rng.gen_range(0.0..total);
+                let target = links
+                    .iter()
+                    .find(|(_, w)| {
+                        pick -= w;
+                        pick < 0.0
+                    })
+                    .map_or(links[links.len() - 1].0, |(i, _)| *i);
+                self.grains[target] += 1;
+            }
+        }
+    }
+}
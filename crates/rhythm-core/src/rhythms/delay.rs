@@ -0,0 +1,55 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// History of the complex states `z = r·e^{iθ}` of a set of oscillators,
+/// one sample per integrator step, newest first.
+///
+/// Delayed values are read by linear interpolation between samples; before
+/// the history is long enough, the oldest sample stands in for the past.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DelayLine {
+    samples: VecDeque<Vec<(f64, f64)>>,
+}
+
+impl DelayLine {
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Number of oscillators in each sample.
+    pub fn width(&self) -> usize {
+        self.samples.front().map_or(0, Vec::len)
+    }
+
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// Append the newest state, keeping at most `capacity` samples.
+    pub fn record(&mut self, state: Vec<(f64, f64)>, capacity: usize) {
+        if state.len() != self.width() {
+            self.samples.clear();
+        }
+        self.samples.push_front(state);
+        self.samples.truncate(capacity.max(1));
+    }
+
+    /// State of oscillator `j` as it was `steps_back` integrator steps ago.
+    pub fn sample(&self, j: usize, steps_back: f64) -> (f64, f64) {
+        let steps_back = steps_back.max(0.0);
+        let last = self.samples.len().saturating_sub(1);
+        let index = (steps_back.floor() as usize).min(last);
+        let newer = self.samples[index][j];
+        if index == last {
+            return newer;
+        }
+
+        let older = self.samples[index + 1][j];
+        let frac = steps_back - index as f64;
+        (
+            newer.0 + (older.0 - newer.0) * frac,
+            newer.1 + (older.1 - newer.1) * frac,
+        )
+    }
+}
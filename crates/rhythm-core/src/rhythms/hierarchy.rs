@@ -0,0 +1,241 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use super::prediction_tension::PredictionTensionParams;
+use super::release::{ReleaseShape, ReleaseState, ReleaseTransition};
+use super::window::{Ewma, SlidingWindow};
+
+/// Buffered errors of one level and the tension and release they drive.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TensionLevel {
+    error_buffer: SlidingWindow,
+    /// Exponentially weighted statistics of the same errors.
+    #[serde(default)]
+    ewma: Ewma,
+    tension_level: f64,
+    release_active: bool,
+    release_intensity: f64,
+    #[serde(default)]
+    release: ReleaseState,
+    /// Time left before another release may start, in s.
+    #[serde(default)]
+    refractory: f64,
+}
+
+impl TensionLevel {
+    pub fn tension(&self) -> f64 {
+        self.tension_level
+    }
+
+    pub fn release_active(&self) -> bool {
+        self.release_active
+    }
+
+    pub fn release_intensity(&self) -> f64 {
+        self.release_intensity
+    }
+
+    /// Time left in the refractory period after the last release, in s.
+    pub fn refractory(&self) -> f64 {
+        self.refractory
+    }
+
+    pub fn buffer_size(&self) -> usize {
+        self.error_buffer.len()
+    }
+
+    /// Population variance of the buffered errors.
+    pub fn variance(&self) -> f64 {
+        self.error_buffer.variance()
+    }
+
+    /// Exponentially weighted statistics of the errors, unaffected by the
+    /// buffer capacity or releases.
+    pub fn ewma(&self) -> &Ewma {
+        &self.ewma
+    }
+
+    pub fn clear(&mut self) {
+        self.error_buffer.clear();
+    }
+
+    /// Drop the oldest errors beyond `capacity`.
+    pub fn truncate(&mut self, capacity: usize) {
+        self.error_buffer.truncate(capacity);
+    }
+
+    /// Buffer `error`, if any, and advance the tension and release by
+    /// `delta_time`.
+    pub fn accumulate(
+        &mut self,
+        error: Option<f64>,
+        delta_time: f64,
+        params: &PredictionTensionParams,
+        shape: &ReleaseShape,
+        rng: &mut impl Rng,
+    ) -> ReleaseTransition {
+        let capacity = params.buffer_capacity;
+        if let Some(error) = error {
+            self.error_buffer.push(error, capacity);
+            self.ewma.observe(error, delta_time, params.ewma_time_constant);
+        }
+
+        // Calculate tension as accumulated error
+        let tension = self.error_buffer.sum() / capacity as f64;
+
+        if self.release_active {
+            // During release, tension drops rapidly
+            self.release_intensity = self.release.step(shape, params.release_decay_time, delta_time, rng);
+            self.tension_level = tension * (-self.release.elapsed() / params.tension_decay_time).exp();
+
+            if self.release.finished() {
+                self.release_active = false;
+                self.release_intensity = 0.0;
+                self.refractory = params.refractory_period;
+                self.error_buffer.clear(); // Reset after release
+                return ReleaseTransition::Ended(self.release);
+            }
+            ReleaseTransition::None
+        } else {
+            self.tension_level = tension;
+            self.refractory = (self.refractory - delta_time).max(0.0);
+            if self.refractory > 0.0 || tension <= params.release_threshold {
+                return ReleaseTransition::None;
+            }
+            // Trigger creative release
+            self.release_active = true;
+            self.release_intensity = tension * params.release_gain;
+            self.release = ReleaseState::new(self.release_intensity, tension);
+            ReleaseTransition::Started
+        }
+    }
+}
+
+/// A level above the first in the predictive hierarchy. Its belief is a
+/// prediction of the belief of the level below; the mismatch is weighted by
+/// a precision learned from its own history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictiveLevel {
+    #[serde(flatten)]
+    tension: TensionLevel,
+    belief: f64,
+    /// Latest error between the belief below and this level's prediction.
+    error: f64,
+    /// Learned variance of the error, the inverse of precision.
+    error_variance: f64,
+}
+
+impl Default for PredictiveLevel {
+    fn default() -> Self {
+        Self {
+            tension: TensionLevel::default(),
+            belief: 0.0,
+            error: 0.0,
+            error_variance: 1.0,
+        }
+    }
+}
+
+/// Variance below which a level's precision stops growing.
+const MIN_VARIANCE: f64 = 1e-9;
+
+impl PredictiveLevel {
+    pub fn tension(&self) -> &TensionLevel {
+        &self.tension
+    }
+
+    pub fn tension_mut(&mut self) -> &mut TensionLevel {
+        &mut self.tension
+    }
+
+    pub fn belief(&self) -> f64 {
+        self.belief
+    }
+
+    pub fn error(&self) -> f64 {
+        self.error
+    }
+
+    pub fn precision(&self) -> f64 {
+        1.0 / self.error_variance.max(MIN_VARIANCE)
+    }
+
+    /// Compare the prediction with `below` and learn the error's variance at
+    /// `learning_rate` per second. Returns the precision-weighted surprise.
+    pub fn predict(&mut self, below: f64, learning_rate: f64, delta_time: f64) -> f64 {
+        self.error = below - self.belief;
+        let surprise = 0.5 * self.precision() * self.error * self.error;
+        let step = (learning_rate * delta_time).min(1.0);
+        self.error_variance += step * (self.error * self.error - self.error_variance);
+        surprise
+    }
+
+    /// Relax the belief at `rate` per second towards the precision-weighted
+    /// mean of the evidence `below` and the prediction `above`, given with
+    /// its precision, if there is a level above.
+    pub fn revise(&mut self, below: f64, above: Option<(f64, f64)>, rate: f64, delta_time: f64) {
+        let precision = self.precision();
+        let target = match above {
+            Some((prediction, above_precision)) => {
+                (precision * below + above_precision * prediction) / (precision + above_precision)
+            }
+            None => below,
+        };
+        self.belief += (rate * delta_time).min(1.0) * (target - self.belief);
+    }
+
+    /// Adopt the evidence from below outright, as after a release.
+    pub fn reset_belief(&mut self, below: f64) {
+        self.belief = below;
+        self.error = 0.0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::rng_from_seed;
+
+    const FRAME: f64 = 1.0 / 60.0;
+
+    #[test]
+    fn default_tension_decay_keeps_old_per_frame_factor() {
+        let params = PredictionTensionParams::default();
+        let mut rng = rng_from_seed(0);
+        let mut level = TensionLevel::default();
+        while level.accumulate(Some(1.0), FRAME, &params, &ReleaseShape::Exponential, &mut rng) != ReleaseTransition::Started {}
+
+        let tension = level.tension();
+        level.accumulate(None, FRAME, &params, &ReleaseShape::Exponential, &mut rng);
+        assert!((level.tension() / tension - 0.9).abs() < 1e-3, "factor {}", level.tension() / tension);
+    }
+
+    #[test]
+    fn no_release_starts_during_the_refractory_period() {
+        let params = PredictionTensionParams {
+            buffer_capacity: 10,
+            release_threshold: 0.5,
+            refractory_period: 1.0,
+            ..PredictionTensionParams::default()
+        };
+        let mut rng = rng_from_seed(0);
+        let mut level = TensionLevel::default();
+        let mut ended_at = None;
+        let mut starts = Vec::new();
+        for frame in 0..1200 {
+            let time = frame as f64 * FRAME;
+            match level.accumulate(Some(1.0), FRAME, &params, &ReleaseShape::Exponential, &mut rng) {
+                ReleaseTransition::Started => {
+                    if let Some(ended_at) = ended_at {
+                        assert!(time - ended_at >= params.refractory_period, "release {} s after the last", time - ended_at);
+                    }
+                    starts.push(time);
+                }
+                ReleaseTransition::Ended(_) => ended_at = Some(time),
+                ReleaseTransition::None => assert!(level.release_active() || level.refractory() > 0.0 || level.tension() <= 0.5),
+            }
+        }
+        // Tension stays above the threshold, so releases resume once the period is over
+        assert!(starts.len() > 2, "{} releases", starts.len());
+    }
+}
@@ -0,0 +1,184 @@
+use nalgebra::DMatrix;
+use serde::{Deserialize, Serialize};
+
+/// Integrated information of a system across its minimum information
+/// partition (MIP), in nats.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IntegratedInformation {
+    /// Stochastic interaction across the MIP: how much less uncertain the
+    /// whole system's next state is than its parts' taken separately. Never
+    /// negative.
+    pub phi: f64,
+    /// Whole-minus-sum time-lagged mutual information across the MIP. Can be
+    /// negative when the parts are redundant.
+    pub whole_minus_sum: f64,
+    /// Time-lagged mutual information of the whole system.
+    pub mutual_information: f64,
+    /// The two sides of the MIP, as node indices.
+    pub partition: [Vec<usize>; 2],
+}
+
+/// Linear Gaussian system `x(t+1) = A x(t) + ε` with unit-variance noise,
+/// observed at its stationary distribution.
+pub struct GaussianSystem {
+    transition: DMatrix<f64>,
+    /// Stationary covariance of `x`.
+    covariance: DMatrix<f64>,
+}
+
+impl GaussianSystem {
+    /// Largest system searched exhaustively; the number of bipartitions
+    /// doubles with every node.
+    pub const MAX_NODES: usize = 10;
+
+    /// System whose transition matrix is `weights` scaled by `gain`, where
+    /// `weights[i][j]` is the influence of node `j` on node `i`.
+    ///
+    /// Weights are first scaled down, if needed, so that no row sums to more
+    /// than 1; with `gain` below 1 the system is then stable.
+    pub fn from_weights(weights: &[Vec<f64>], gain: f64) -> Result<Self, String> {
+        let n = weights.len();
+        if n == 0 || weights.iter().any(|row| row.len() != n) {
+            return Err("Weights must be a non-empty square matrix".to_string());
+        }
+        if !(0.0..1.0).contains(&gain) {
+            return Err(format!("Gain must be in [0, 1), got {}", gain));
+        }
+
+        let max_row_sum = weights
+            .iter()
+            .map(|row| row.iter().map(|w| w.abs()).sum::<f64>())
+            .fold(1.0, f64::max);
+        let transition = DMatrix::from_fn(n, n, |i, j| gain * weights[i][j] / max_row_sum);
+
+        Ok(Self {
+            covariance: stationary_covariance(&transition),
+            transition,
+        })
+    }
+
+    pub fn size(&self) -> usize {
+        self.transition.nrows()
+    }
+
+    /// Search every bipartition for the one that loses the least information
+    /// relative to the size of its smaller side.
+    ///
+    /// Returns `None` for a single node or a system larger than `MAX_NODES`.
+    pub fn integrated_information(&self) -> Option<IntegratedInformation> {
+        let n = self.size();
+        if !(2..=Self::MAX_NODES).contains(&n) {
+            return None;
+        }
+
+        let all: Vec<usize> = (0..n).collect();
+        let whole_entropy = self.conditional_log_det(&all)?;
+        let mutual_information = self.mutual_information(&all)?;
+
+        let mut best: Option<(f64, IntegratedInformation)> = None;
+        // The last node always sits in the second part, so each bipartition is seen once
+        for mask in 1..(1u32 << (n - 1)) {
+            let (first, second): (Vec<usize>, Vec<usize>) =
+                all.iter().copied().partition(|&i| mask & (1 << i) != 0);
+
+            let phi = 0.5
+                * (self.conditional_log_det(&first)? + self.conditional_log_det(&second)? - whole_entropy);
+            let normalized = phi / first.len().min(second.len()) as f64;
+            if best.as_ref().map_or(true, |(lowest, _)| normalized < *lowest) {
+                let whole_minus_sum =
+                    mutual_information - self.mutual_information(&first)? - self.mutual_information(&second)?;
+                best = Some((
+                    normalized,
+                    IntegratedInformation {
+                        phi: phi.max(0.0),
+                        whole_minus_sum,
+                        mutual_information,
+                        partition: [first, second],
+                    },
+                ));
+            }
+        }
+
+        best.map(|(_, information)| information)
+    }
+
+    /// `log det` of the covariance of `nodes` at `t + 1` given their own
+    /// state at `t`, i.e. twice their conditional entropy up to a constant.
+    fn conditional_log_det(&self, nodes: &[usize]) -> Option<f64> {
+        let sigma = self.covariance.select_rows(nodes).select_columns(nodes);
+        // Cov(x(t+1), x(t)) = A Σ
+        let lagged = (&self.transition * &self.covariance).select_rows(nodes).select_columns(nodes);
+        let explained = &lagged * sigma.clone().cholesky()?.inverse() * lagged.transpose();
+        log_det(sigma - explained)
+    }
+
+    /// Mutual information between the state of `nodes` at `t` and `t + 1`.
+    fn mutual_information(&self, nodes: &[usize]) -> Option<f64> {
+        let sigma = self.covariance.select_rows(nodes).select_columns(nodes);
+        Some(0.5 * (log_det(sigma)? - self.conditional_log_det(nodes)?))
+    }
+}
+
+/// Solve `Σ = A Σ Aᵀ + I` by doubling, which converges for any stable `A`.
+fn stationary_covariance(transition: &DMatrix<f64>) -> DMatrix<f64> {
+    let n = transition.nrows();
+    let mut covariance = DMatrix::identity(n, n);
+    let mut power = transition.clone();
+    for _ in 0..64 {
+        let added = &power * &covariance * power.transpose();
+        covariance += &added;
+        power = &power * &power;
+        if added.norm() < 1e-14 * covariance.norm() {
+            break;
+        }
+    }
+    covariance
+}
+
+fn log_det(matrix: DMatrix<f64>) -> Option<f64> {
+    let cholesky = matrix.cholesky()?;
+    Some(2.0 * cholesky.l().diagonal().iter().map(|d| d.ln()).sum::<f64>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two pairs of mutually coupled nodes, joined by `bridge` from node 1 to node 2.
+    fn two_pairs(bridge: f64) -> GaussianSystem {
+        let weights = vec![
+            vec![0.2, 0.6, 0.0, 0.0],
+            vec![0.6, 0.2, 0.0, 0.0],
+            vec![0.0, bridge, 0.2, 0.6],
+            vec![0.0, 0.0, 0.6, 0.2],
+        ];
+        GaussianSystem::from_weights(&weights, 0.9).unwrap()
+    }
+
+    #[test]
+    fn disconnected_system_has_no_integrated_information() {
+        let system = two_pairs(0.0);
+        let covariance = &system.covariance;
+        assert!((0..2).all(|i| (2..4).all(|j| covariance[(i, j)] == 0.0)));
+
+        let information = system.integrated_information().unwrap();
+        assert!(information.phi.abs() < 1e-12, "phi {}", information.phi);
+        assert!(information.whole_minus_sum.abs() < 1e-12);
+        assert_eq!(information.partition, [vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn coupled_system_has_integrated_information() {
+        let information = two_pairs(0.3).integrated_information().unwrap();
+        assert!(information.phi > 1e-3, "phi {}", information.phi);
+        // The weak bridge is still the cheapest cut
+        assert_eq!(information.partition, [vec![0, 1], vec![2, 3]]);
+    }
+
+    #[test]
+    fn too_large_systems_are_not_searched() {
+        let n = GaussianSystem::MAX_NODES + 1;
+        let system = GaussianSystem::from_weights(&vec![vec![0.05; n]; n], 0.5).unwrap();
+        assert_eq!(system.integrated_information(), None);
+    }
+}
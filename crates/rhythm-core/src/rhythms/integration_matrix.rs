@@ -0,0 +1,173 @@
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::standard_normal;
+
+/// Distribution the initial weights of the integration matrix are drawn
+/// from. Draws are clamped to [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum WeightDistribution {
+    /// Bounds in [0, 1].
+    Uniform { low: f64, high: f64 },
+    Normal { mean: f64, std_dev: f64 },
+    /// `exp` of a normal draw; heavy-tailed, with a few strong links.
+    LogNormal { mu: f64, sigma: f64 },
+}
+
+impl Default for WeightDistribution {
+    fn default() -> Self {
+        WeightDistribution::Uniform { low: 0.0, high: 0.5 }
+    }
+}
+
+impl WeightDistribution {
+    fn validate(&self) -> Result<(), String> {
+        match *self {
+            WeightDistribution::Uniform { low, high } if !(0.0..=1.0).contains(&low) || !(0.0..=1.0).contains(&high) => {
+                Err(format!("low and high must be in [0, 1], got {} and {}", low, high))
+            }
+            WeightDistribution::Uniform { low, high } if low > high => {
+                Err(format!("low must not exceed high, got {} and {}", low, high))
+            }
+            WeightDistribution::Normal { mean: center, std_dev: spread }
+            | WeightDistribution::LogNormal { mu: center, sigma: spread }
+                if !(center.is_finite() && spread >= 0.0 && spread.is_finite()) =>
+            {
+                Err(format!(
+                    "Center must be finite and spread finite and non-negative, got {} and {}",
+                    center, spread
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn sample(&self, rng: &mut impl Rng) -> f64 {
+        let weight = match *self {
+            WeightDistribution::Uniform { low, high } if low < high => rng.gen_range(low..high),
+            WeightDistribution::Uniform { low, .. } => low,
+            WeightDistribution::Normal { mean, std_dev } => mean + std_dev * standard_normal(rng),
+            WeightDistribution::LogNormal { mu, sigma } => (mu + sigma * standard_normal(rng)).exp(),
+        };
+        weight.clamp(0.0, 1.0)
+    }
+}
+
+/// How the weights of existing links change over time; absent links stay
+/// absent. Rates are per second and weights stay within [0, 1].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Plasticity {
+    Frozen,
+    /// Independent uniform noise of at most `rate` on every weight.
+    Noise { rate: f64 },
+    /// `dw_ij = learning_rate · φ · x_i · x_j − decay · w_ij`, where `x` is
+    /// node activity: links between co-active nodes strengthen in
+    /// proportion to phi and all links slowly decay.
+    Hebbian { learning_rate: f64, decay: f64 },
+    /// Synaptic scaling `dw_ij = rate · (φ_target − φ) · w_ij`: every weight
+    /// shrinks while phi is above its target and grows while it is below.
+    Homeostatic { rate: f64 },
+}
+
+impl Default for Plasticity {
+    fn default() -> Self {
+        Plasticity::Noise { rate: 0.01 }
+    }
+}
+
+impl Plasticity {
+    /// Largest rate, per second, of any kind of plasticity.
+    pub const MAX_RATE: f64 = 100.0;
+
+    fn validate(&self) -> Result<(), String> {
+        let rates: &[f64] = match self {
+            Plasticity::Frozen => &[],
+            Plasticity::Noise { rate } | Plasticity::Homeostatic { rate } => std::slice::from_ref(rate),
+            Plasticity::Hebbian { learning_rate, decay } => &[*learning_rate, *decay],
+        };
+        match rates.iter().find(|rate| !(0.0..=Self::MAX_RATE).contains(*rate)) {
+            Some(rate) => Err(format!("Plasticity rates must be in [0, {}], got {}", Self::MAX_RATE, rate)),
+            None => Ok(()),
+        }
+    }
+
+    /// Rate of change of a link of weight `weight` between nodes whose
+    /// activities multiply to `coactivity`.
+    pub fn rate(&self, weight: f64, coactivity: f64, phi: f64, phi_target: f64, rng: &mut impl Rng) -> f64 {
+        match *self {
+            Plasticity::Frozen => 0.0,
+            Plasticity::Noise { rate } if rate > 0.0 => rng.gen_range(-rate..rate),
+            Plasticity::Noise { .. } => 0.0,
+            Plasticity::Hebbian { learning_rate, decay } => learning_rate * phi * coactivity - decay * weight,
+            Plasticity::Homeostatic { rate } => rate * (phi_target - phi) * weight,
+        }
+    }
+}
+
+/// Shape of the critical-phi integration matrix.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MatrixConfig {
+    /// Number of nodes. Integrated information is only estimated up to
+    /// `GaussianSystem::MAX_NODES`, and its cost doubles with every node.
+    pub size: usize,
+    pub distribution: WeightDistribution,
+    /// Fraction of links left out, in [0, 1].
+    pub sparsity: f64,
+    /// Whether each node also influences itself. The sandpile engine never
+    /// topples grains onto the node they leave.
+    pub self_links: bool,
+    pub plasticity: Plasticity,
+}
+
+impl Default for MatrixConfig {
+    fn default() -> Self {
+        Self {
+            size: 5,
+            distribution: WeightDistribution::default(),
+            sparsity: 0.0,
+            self_links: true,
+            plasticity: Plasticity::default(),
+        }
+    }
+}
+
+impl MatrixConfig {
+    pub const MAX_SIZE: usize = 64;
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=Self::MAX_SIZE).contains(&self.size) {
+            return Err(format!("size must be in [1, {}], got {}", Self::MAX_SIZE, self.size));
+        }
+        if !(0.0..=1.0).contains(&self.sparsity) {
+            return Err(format!("sparsity must be in [0, 1], got {}", self.sparsity));
+        }
+        self.distribution.validate()?;
+        self.plasticity.validate()
+    }
+
+    /// Draw a weight matrix and the links it has; `weights[i][j]` is the
+    /// influence of node `j` on node `i`.
+    pub fn generate(&self, rng: &mut impl Rng) -> (Vec<Vec<f64>>, Vec<Vec<bool>>) {
+        let n = self.size;
+        let mut weights = vec![vec![0.0; n]; n];
+        let mut links = vec![vec![false; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                if (i == j && !self.self_links) || (self.sparsity > 0.0 && rng.gen_bool(self.sparsity)) {
+                    continue;
+                }
+                links[i][j] = true;
+                weights[i][j] = self.distribution.sample(rng);
+            }
+        }
+        (weights, links)
+    }
+}
+
+/// Every link of an `n`-node matrix, self-links included.
+pub fn dense_links(n: usize) -> Vec<Vec<bool>> {
+    vec![vec![true; n]; n]
+}
@@ -0,0 +1,123 @@
+pub mod multi_temporal;
+pub mod critical_phi;
+pub mod prediction_tension;
+pub mod semantic_vortex;
+pub mod attention_wandering;
+pub mod avalanche;
+pub mod coupling;
+pub mod criticality;
+pub mod delay;
+pub mod hierarchy;
+pub mod integrated_information;
+pub mod integration_matrix;
+pub mod oscillator_bank;
+pub mod params;
+pub mod power_law;
+pub mod predictor;
+pub mod registry;
+pub mod release;
+pub mod schema;
+pub mod signal;
+pub mod synchrony;
+pub mod window;
+
+pub use multi_temporal::{MultiTemporalRhythm, OscillatorModel};
+pub use critical_phi::CriticalPhi;
+pub use prediction_tension::{InferenceMetrics, PredictionStep, PredictionTension};
+pub use semantic_vortex::SemanticVortex;
+pub use attention_wandering::AttentionWandering;
+pub use avalanche::{Avalanche, AvalancheLog, AvalancheStatistics};
+pub use coupling::{CouplingMatrix, Topology};
+pub use criticality::{ActivityNetwork, CriticalityEngine};
+pub use delay::DelayLine;
+pub use hierarchy::{PredictiveLevel, TensionLevel};
+pub use integrated_information::{GaussianSystem, IntegratedInformation};
+pub use integration_matrix::{MatrixConfig, Plasticity, WeightDistribution};
+pub use oscillator_bank::{Oscillator, OscillatorBank, OscillatorUpdate};
+pub use params::{ParameterSpec, RhythmParams};
+pub use power_law::{HistogramBin, LogHistogram, PowerLawFit};
+pub use predictor::{Prediction, Predictor, PredictorConfig};
+pub use registry::RhythmRegistry;
+pub use release::{ReleaseEvent, ReleaseLog, ReleaseShape, ReleaseState, ReleaseTransition};
+pub use schema::{ChannelSpec, FieldType, MetadataField, OutputSchema, RhythmDescription};
+pub use signal::{SignalInput, SignalSource};
+pub use synchrony::{OrderParameter, SyncState, SyncTracker};
+pub use window::{Ewma, SlidingWindow};
+
+use std::any::Any;
+
+use crate::integrator::IntegrationScheme;
+use crate::RhythmData;
+
+/// Common interface for every rhythm model driven by the backend.
+pub trait Rhythm: Send {
+    /// Identifier used by the frontend to address this rhythm.
+    fn name(&self) -> &'static str;
+
+    /// Seed the rhythm's random stream was created from.
+    fn seed(&self) -> u64;
+
+    /// Reset the rhythm to the initial state generated from `seed`.
+    fn reseed(&mut self, seed: u64);
+
+    /// Advance the rhythm by `delta_time` seconds.
+    fn update(&mut self, delta_time: f64);
+
+    /// Capture the current state as a frame for the frontend.
+    fn snapshot(&self, timestamp: f64) -> RhythmData;
+
+    /// Channel values of the current frame, without building its metadata.
+    fn output_values(&self) -> Vec<f64>;
+
+    /// Names, units and ranges of the values and metadata in each frame.
+    fn output_schema(&self) -> OutputSchema;
+
+    /// Tunable parameters exposed by this rhythm.
+    fn parameter_schema(&self) -> Vec<ParameterSpec>;
+
+    /// Current parameter values as a JSON object.
+    fn params(&self) -> serde_json::Value;
+
+    /// Apply a partial parameter update and return the resulting values.
+    ///
+    /// The update is rejected as a whole if any field is unknown or out of range.
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String>;
+
+    /// Complete internal state, including the random stream position.
+    fn save_state(&self) -> Result<serde_json::Value, String>;
+
+    /// Replace the internal state with one produced by `save_state`.
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String>;
+
+    /// Scheme used for the continuous part of the dynamics, or `None` if the
+    /// rhythm has no selectable integrator.
+    fn integration_scheme(&self) -> Option<IntegrationScheme> {
+        None
+    }
+
+    fn set_integration_scheme(&mut self, _scheme: IntegrationScheme) -> Result<(), String> {
+        Err(format!("{} does not support selectable integration schemes", self.name()))
+    }
+
+    /// Output channel of another rhythm this one consumes, as its id and
+    /// channel index.
+    fn input_source(&self) -> Option<(String, usize)> {
+        None
+    }
+
+    /// Value of the input source at the start of the tick, delivered just
+    /// before `update`.
+    fn receive_input(&mut self, _value: f64) {}
+
+    /// Concrete rhythm, for operations specific to one model.
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+
+    fn describe(&self) -> RhythmDescription {
+        RhythmDescription {
+            rhythm_type: self.name().to_string(),
+            output: self.output_schema(),
+            parameters: self.parameter_schema(),
+            integration_scheme: self.integration_scheme(),
+        }
+    }
+}
@@ -0,0 +1,726 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{ChannelSpec, FieldType, MetadataField, OutputSchema, ParameterSpec, Rhythm, RhythmParams};
+use super::coupling::{CouplingMatrix, Topology};
+use super::delay::DelayLine;
+use super::oscillator_bank::{Oscillator, OscillatorBank, OscillatorUpdate};
+use super::synchrony::{OrderParameter, SyncState, SyncTracker};
+use crate::clock::ElapsedTime;
+use crate::integrator::{IntegrationScheme, Integrator};
+use crate::RhythmData;
+use crate::rng::{random_seed, rng_from_seed, RhythmRng};
+use std::f64::consts::PI;
+use rand::Rng;
+use std::any::Any;
+
+/// Phase diffusion per sqrt(second) for a `chaos_factor` of 1.
+const CHAOS_DIFFUSION: f64 = 0.08;
+
+/// Split a phase into whole turns and a remainder in [0, 2π).
+fn wrap_phase(phase: f64) -> (i64, f64) {
+    let turns = (phase / (2.0 * PI)).floor();
+    let wrapped = phase - turns * 2.0 * PI;
+    // Rounding can land a tiny negative phase exactly on 2π
+    if wrapped >= 2.0 * PI {
+        (turns as i64 + 1, 0.0)
+    } else {
+        (turns as i64, wrapped.max(0.0))
+    }
+}
+
+/// Delayed state `z_j` seen by oscillator `i`, for pairs coupled with a delay.
+type DelayedInputs = Vec<Vec<Option<(f64, f64)>>>;
+
+/// Dynamics of each oscillator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OscillatorModel {
+    /// Kuramoto phase oscillators on a fixed unit circle.
+    PhaseOnly,
+    /// Stuart-Landau (Hopf normal form) oscillators with complex amplitude
+    /// `z = r·e^{iθ}` obeying `dz/dt = (μ + iω - |z|²) z` plus diffusive
+    /// coupling `K Σ w·e^{-iα} (z_j - z_i)`. Below `μ = 0` an isolated
+    /// oscillator decays to rest; strong coupling between mismatched
+    /// frequencies can quench oscillations entirely.
+    StuartLandau,
+}
+
+impl Default for OscillatorModel {
+    fn default() -> Self {
+        OscillatorModel::PhaseOnly
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MultiTemporalParams {
+    /// Kuramoto coupling between oscillators, in rad/s.
+    pub coupling_strength: f64,
+    /// Averaging time of the synchronization metrics, in s.
+    pub sync_window: f64,
+    /// Mean order parameter at or above which the oscillators count as synchronized.
+    pub sync_threshold: f64,
+    /// Mean order parameter below which the oscillators count as incoherent.
+    pub incoherence_threshold: f64,
+    /// Hopf bifurcation parameter μ of the Stuart-Landau model, in 1/s.
+    pub bifurcation: f64,
+}
+
+impl Default for MultiTemporalParams {
+    fn default() -> Self {
+        Self {
+            coupling_strength: 0.2,
+            sync_window: 10.0,
+            sync_threshold: 0.8,
+            incoherence_threshold: 0.3,
+            bifurcation: 1.0,
+        }
+    }
+}
+
+impl RhythmParams for MultiTemporalParams {
+    fn schema() -> Vec<ParameterSpec> {
+        let defaults = Self::default();
+        vec![
+            ParameterSpec::new("coupling_strength", "Kuramoto coupling between oscillators", defaults.coupling_strength, 0.0, 2.0, "rad/s"),
+            ParameterSpec::new("sync_window", "Averaging time of the synchronization metrics", defaults.sync_window, 0.1, 1000.0, "s"),
+            ParameterSpec::new("sync_threshold", "Mean order parameter for the synchronized state", defaults.sync_threshold, 0.0, 1.0, ""),
+            ParameterSpec::new("incoherence_threshold", "Mean order parameter below which the state is incoherent", defaults.incoherence_threshold, 0.0, 1.0, ""),
+            ParameterSpec::new("bifurcation", "Stuart-Landau bifurcation parameter; oscillations need it above zero", defaults.bifurcation, -2.0, 2.0, "1/s"),
+        ]
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.incoherence_threshold > self.sync_threshold {
+            return Err("incoherence_threshold must not exceed sync_threshold".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MultiTemporalRhythm {
+    /// Configuration the oscillators start from on creation and reseed.
+    #[serde(default)]
+    bank: OscillatorBank,
+    oscillators: Vec<Oscillator>,
+    /// Whole turns each oscillator has completed; its unwrapped phase is
+    /// `2π·cycles + phase`.
+    #[serde(default)]
+    cycles: Vec<i64>,
+    /// Radius `|z|` of each oscillator, fixed at 1 without amplitude dynamics.
+    #[serde(default)]
+    radii: Vec<f64>,
+    #[serde(default)]
+    model: OscillatorModel,
+    params: MultiTemporalParams,
+    #[serde(default)]
+    coupling: CouplingMatrix,
+    /// Past oscillator states, kept only while some coupling is delayed.
+    #[serde(default)]
+    history: DelayLine,
+    #[serde(default)]
+    sync: SyncTracker,
+    time: ElapsedTime,
+    integrator: Integrator,
+    seed: u64,
+    rng: RhythmRng,
+}
+
+impl Default for MultiTemporalRhythm {
+    fn default() -> Self {
+        Self::from_seed(random_seed())
+    }
+}
+
+impl MultiTemporalRhythm {
+    /// Create the rhythm with all randomness drawn from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        let bank = OscillatorBank::default();
+        Self {
+            coupling: CouplingMatrix::all_to_all(bank.oscillators.len()),
+            oscillators: bank.oscillators.clone(),
+            cycles: vec![0; bank.oscillators.len()],
+            radii: vec![1.0; bank.oscillators.len()],
+            model: OscillatorModel::default(),
+            bank,
+            params: MultiTemporalParams::default(),
+            history: DelayLine::default(),
+            sync: SyncTracker::default(),
+            time: ElapsedTime::default(),
+            integrator: Integrator::default(),
+            seed,
+            rng: rng_from_seed(seed),
+        }
+    }
+
+    /// Oscillators in their current state.
+    pub fn oscillators(&self) -> &[Oscillator] {
+        &self.oscillators
+    }
+
+    /// Bank the oscillators are reset to on reseed.
+    pub fn bank(&self) -> &OscillatorBank {
+        &self.bank
+    }
+
+    /// Replace every oscillator, restarting them from the bank's phases.
+    pub fn set_bank(&mut self, bank: OscillatorBank) -> Result<(), String> {
+        bank.validate()?;
+        self.oscillators = bank.oscillators.clone();
+        self.cycles = vec![0; self.oscillators.len()];
+        self.radii = vec![1.0; self.oscillators.len()];
+        self.bank = bank;
+        self.wrap_phases();
+        self.sync = SyncTracker::default();
+        self.history.clear();
+        if self.coupling.topology.is_some() {
+            self.regenerate_coupling()?;
+        } else if self.coupling.size() != self.oscillators.len() {
+            self.coupling = CouplingMatrix::all_to_all(self.oscillators.len());
+        }
+        Ok(())
+    }
+
+    pub fn add_oscillator(&mut self, oscillator: Oscillator) -> Result<(), String> {
+        let mut bank = self.bank.clone();
+        bank.oscillators.push(oscillator.clone());
+        bank.validate()?;
+        self.bank = bank;
+        self.oscillators.push(oscillator);
+        self.cycles.push(0);
+        self.radii.push(1.0);
+        self.wrap_phases();
+        if self.coupling.topology.is_some() {
+            self.regenerate_coupling()?;
+        } else {
+            self.coupling.push();
+        }
+        Ok(())
+    }
+
+    pub fn remove_oscillator(&mut self, index: usize) -> Result<Oscillator, String> {
+        self.check_index(index)?;
+        if self.oscillators.len() == 1 {
+            return Err("Cannot remove the last oscillator".to_string());
+        }
+        self.bank.oscillators.remove(index);
+        let removed = self.oscillators.remove(index);
+        self.cycles.remove(index);
+        self.radii.remove(index);
+        if self.coupling.topology.is_some() {
+            self.regenerate_coupling()?;
+        } else {
+            self.coupling.remove(index);
+        }
+        Ok(removed)
+    }
+
+    /// Edit one oscillator in place; its running phase is only replaced when
+    /// the update sets one.
+    pub fn update_oscillator(&mut self, index: usize, update: &OscillatorUpdate) -> Result<&Oscillator, String> {
+        self.check_index(index)?;
+        let running = update.apply(&self.oscillators[index])?;
+        self.bank.oscillators[index] = update.apply(&self.bank.oscillators[index])?;
+        self.oscillators[index] = running;
+        self.wrap_phases();
+        if update.frequency.is_some() && matches!(self.coupling.topology, Some(Topology::Hierarchical { .. })) {
+            self.regenerate_coupling()?;
+        }
+        Ok(&self.oscillators[index])
+    }
+
+    pub fn model(&self) -> OscillatorModel {
+        self.model
+    }
+
+    /// Switch between phase-only and Stuart-Landau dynamics. Every oscillator
+    /// restarts from unit radius.
+    pub fn set_model(&mut self, model: OscillatorModel) {
+        self.model = model;
+        self.radii = vec![1.0; self.oscillators.len()];
+        self.history.clear();
+    }
+
+    /// Radius of each oscillator's complex amplitude.
+    pub fn radii(&self) -> &[f64] {
+        &self.radii
+    }
+
+    pub fn coupling(&self) -> &CouplingMatrix {
+        &self.coupling
+    }
+
+    /// Use a coupling matrix sized for the current oscillators.
+    ///
+    /// A matrix without a topology is kept as the oscillators change: removed
+    /// oscillators drop out of it and added ones get unit coupling to all.
+    pub fn set_coupling(&mut self, coupling: CouplingMatrix) -> Result<(), String> {
+        coupling.validate(self.oscillators.len())?;
+        self.coupling = coupling;
+        Ok(())
+    }
+
+    /// Generate the coupling from `topology`, with `phase_lag` and `delay`
+    /// between every pair. It is regenerated whenever the oscillators are
+    /// added or removed.
+    pub fn generate_coupling(&mut self, topology: Topology, phase_lag: f64, delay: f64) -> Result<(), String> {
+        let frequencies = self.frequencies();
+        self.coupling = CouplingMatrix::generate(topology, phase_lag, delay, &frequencies, &mut self.rng)?;
+        Ok(())
+    }
+
+    fn regenerate_coupling(&mut self) -> Result<(), String> {
+        if let Some(topology) = self.coupling.topology.clone() {
+            self.generate_coupling(topology, self.coupling.phase_lag, self.coupling.delay)?;
+        }
+        Ok(())
+    }
+
+    fn frequencies(&self) -> Vec<f64> {
+        self.oscillators.iter().map(|osc| osc.frequency).collect()
+    }
+
+    /// Cumulative phase of each oscillator since it started, in rad.
+    pub fn unwrapped_phases(&self) -> Vec<f64> {
+        self.oscillators
+            .iter()
+            .zip(&self.cycles)
+            .map(|(osc, cycles)| 2.0 * PI * *cycles as f64 + osc.phase)
+            .collect()
+    }
+
+    /// Check state read back from a snapshot before it replaces the current one.
+    fn validate(&self) -> Result<(), String> {
+        let n = self.oscillators.len();
+        OscillatorBank::new(self.oscillators.clone())?;
+        self.bank.validate()?;
+        if self.cycles.len() != n || self.radii.len() != n {
+            return Err(format!("cycles and radii must have {} entries", n));
+        }
+        if self.radii.iter().any(|radius| !(*radius >= 0.0 && radius.is_finite())) {
+            return Err("Radii must be finite and non-negative".to_string());
+        }
+        self.coupling.validate(n)?;
+        self.params.check()?;
+        self.integrator.validate()
+    }
+
+    /// Bring phases set from outside into [0, 2π). Whole turns of a phase
+    /// that was set are not turns completed, so the cycle counts stay.
+    fn wrap_phases(&mut self) {
+        for osc in &mut self.oscillators {
+            osc.phase = wrap_phase(osc.phase).1;
+        }
+    }
+
+    /// Complex amplitude `z = r·e^{iθ}` of each oscillator.
+    fn complex_state(&self) -> Vec<(f64, f64)> {
+        self.oscillators
+            .iter()
+            .zip(&self.radii)
+            .map(|(osc, r)| (r * osc.phase.cos(), r * osc.phase.sin()))
+            .collect()
+    }
+
+    fn phases(&self) -> Vec<f64> {
+        self.oscillators.iter().map(|osc| osc.phase).collect()
+    }
+
+    /// Classify the current regime from the time-averaged order parameter.
+    pub fn sync_state(&self) -> SyncState {
+        SyncState::classify(
+            self.sync.mean_order(),
+            self.params.incoherence_threshold,
+            self.params.sync_threshold,
+        )
+    }
+
+    fn check_index(&self, index: usize) -> Result<(), String> {
+        if index >= self.oscillators.len() {
+            return Err(format!(
+                "Oscillator index {} out of range, there are {}",
+                index,
+                self.oscillators.len()
+            ));
+        }
+        Ok(())
+    }
+
+    fn integrate_step(&mut self, h: f64) {
+        let delayed = self.delayed_inputs(h);
+        match self.model {
+            OscillatorModel::PhaseOnly => self.integrate_phases(h, delayed.as_ref()),
+            OscillatorModel::StuartLandau => self.integrate_stuart_landau(h, delayed.as_ref()),
+        }
+        self.record_history(h);
+    }
+
+    /// Delayed states for a step of `h` starting now, or `None` if no
+    /// coupling is delayed.
+    ///
+    /// Delays are resolved against a history with one sample per fixed
+    /// integrator step, so they do not depend on the clock's tick rate or
+    /// time scale. Each delayed input is read half a step ahead and held for
+    /// the whole step, which keeps the scheme explicit: delays shorter than
+    /// half a step act as instantaneous coupling.
+    fn delayed_inputs(&mut self, h: f64) -> Option<DelayedInputs> {
+        if self.coupling.max_delay() == 0.0 {
+            self.history.clear();
+            return None;
+        }
+        if self.history.width() != self.oscillators.len() {
+            let state = self.complex_state();
+            self.history.record(state, 1);
+        }
+
+        let history = &self.history;
+        let inputs = self
+            .coupling
+            .delays
+            .iter()
+            .zip(&self.coupling.weights)
+            .map(|(delays, weights)| {
+                delays
+                    .iter()
+                    .zip(weights)
+                    .enumerate()
+                    .map(|(j, (&delay, &weight))| {
+                        if delay > 0.0 && weight != 0.0 {
+                            Some(history.sample(j, (delay - h / 2.0) / h))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        Some(inputs)
+    }
+
+    fn record_history(&mut self, h: f64) {
+        let max_delay = self.coupling.max_delay();
+        if max_delay > 0.0 {
+            let capacity = (max_delay / h).ceil() as usize + 2;
+            let state = self.complex_state();
+            self.history.record(state, capacity);
+        }
+    }
+
+    fn integrate_phases(&mut self, h: f64, delayed: Option<&DelayedInputs>) {
+        let coupling_strength = self.params.coupling_strength;
+        let coupling = &self.coupling;
+        let frequencies = self.frequencies();
+        let mut phases = self.phases();
+        let delayed_phases: Option<Vec<Vec<Option<f64>>>> = delayed.map(|delayed| {
+            delayed
+                .iter()
+                .map(|row| row.iter().map(|z| z.map(|(x, y)| y.atan2(x))).collect())
+                .collect()
+        });
+
+        // Natural frequency plus Sakaguchi-Kuramoto coupling from the other oscillators
+        self.integrator.scheme.step_first_order(&mut phases, h, |phases, i| {
+            let coupling_effect: f64 = phases
+                .iter()
+                .zip(&coupling.weights[i])
+                .zip(&coupling.lags[i])
+                .enumerate()
+                .map(|(j, ((phase, weight), lag))| {
+                    let phase = delayed_phases.as_ref().and_then(|d| d[i][j]).unwrap_or(*phase);
+                    weight * (phase - phases[i] - lag).sin()
+                })
+                .sum();
+            2.0 * PI * frequencies[i] + coupling_strength * coupling_effect
+        });
+
+        for ((osc, cycles), phase) in self.oscillators.iter_mut().zip(&mut self.cycles).zip(phases) {
+            // Add chaotic perturbation
+            let chaos = self.rng.gen_range(-1.0..1.0) * osc.chaos_factor * CHAOS_DIFFUSION * h.sqrt();
+
+            // Keep phase in [0, 2π) and count the turns it made
+            let (turns, wrapped) = wrap_phase(phase + chaos);
+            *cycles += turns;
+            osc.phase = wrapped;
+        }
+    }
+
+    fn integrate_stuart_landau(&mut self, h: f64, delayed: Option<&DelayedInputs>) {
+        let coupling_strength = self.params.coupling_strength;
+        let mu = self.params.bifurcation;
+        let coupling = &self.coupling;
+        let omegas: Vec<f64> = self.frequencies().iter().map(|f| 2.0 * PI * f).collect();
+
+        // Interleaved real and imaginary parts of each z
+        let mut state: Vec<f64> = self
+            .oscillators
+            .iter()
+            .zip(&self.radii)
+            .flat_map(|(osc, r)| [r * osc.phase.cos(), r * osc.phase.sin()])
+            .collect();
+
+        self.integrator.scheme.step_first_order(&mut state, h, |z, k| {
+            let i = k / 2;
+            let (x, y) = (z[2 * i], z[2 * i + 1]);
+            let growth = mu - (x * x + y * y);
+
+            // (μ + iω - |z|²) z
+            let mut dx = growth * x - omegas[i] * y;
+            let mut dy = growth * y + omegas[i] * x;
+
+            // K Σ w·e^{-iα} (z_j - z_i)
+            for (j, (weight, lag)) in coupling.weights[i].iter().zip(&coupling.lags[i]).enumerate() {
+                if *weight == 0.0 {
+                    continue;
+                }
+                let (x_j, y_j) = delayed.and_then(|d| d[i][j]).unwrap_or((z[2 * j], z[2 * j + 1]));
+                let (diff_x, diff_y) = (x_j - x, y_j - y);
+                let (cos, sin) = (lag.cos(), lag.sin());
+                dx += coupling_strength * weight * (cos * diff_x + sin * diff_y);
+                dy += coupling_strength * weight * (cos * diff_y - sin * diff_x);
+            }
+
+            if k % 2 == 0 {
+                dx
+            } else {
+                dy
+            }
+        });
+
+        let oscillators = self.oscillators.iter_mut().zip(&mut self.cycles).zip(&mut self.radii);
+        for (((osc, cycles), radius), z) in oscillators.zip(state.chunks(2)) {
+            // Additive complex noise; at unit radius it matches the phase-only perturbation
+            let scale = osc.chaos_factor * CHAOS_DIFFUSION * h.sqrt();
+            let x = z[0] + self.rng.gen_range(-1.0..1.0) * scale;
+            let y = z[1] + self.rng.gen_range(-1.0..1.0) * scale;
+            *radius = x.hypot(y);
+
+            // A step turns z by well under half a turn, so the nearest
+            // equivalent angle continues the previous phase
+            let turn = y.atan2(x) - osc.phase;
+            let turn = turn - 2.0 * PI * (turn / (2.0 * PI)).round();
+            let (turns, wrapped) = wrap_phase(osc.phase + turn);
+            *cycles += turns;
+            osc.phase = wrapped;
+        }
+    }
+}
+
+impl Rhythm for MultiTemporalRhythm {
+    fn name(&self) -> &'static str {
+        "multi_temporal"
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        let params = self.params.clone();
+        let scheme = self.integrator.scheme;
+        let model = self.model;
+        let bank = self.bank.clone();
+        let coupling = self.coupling.clone();
+        *self = Self::from_seed(seed);
+        self.params = params;
+        self.integrator.scheme = scheme;
+        self.oscillators = bank.oscillators.clone();
+        self.cycles = vec![0; self.oscillators.len()];
+        self.radii = vec![1.0; self.oscillators.len()];
+        self.bank = bank;
+        self.coupling = coupling;
+        self.model = model;
+        self.wrap_phases();
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        self.time.advance(delta_time);
+
+        for _ in 0..self.integrator.substeps(delta_time) {
+            let h = self.integrator.step();
+            self.integrate_step(h);
+            let phases = self.phases();
+            self.sync.observe(&phases, h, self.params.sync_window);
+        }
+    }
+
+    fn integration_scheme(&self) -> Option<IntegrationScheme> {
+        Some(self.integrator.scheme)
+    }
+
+    fn set_integration_scheme(&mut self, scheme: IntegrationScheme) -> Result<(), String> {
+        self.integrator.scheme = scheme;
+        Ok(())
+    }
+    
+    fn output_values(&self) -> Vec<f64> {
+        self.oscillators
+            .iter()
+            .zip(&self.radii)
+            .map(|(osc, radius)| osc.amplitude * radius * osc.phase.sin())
+            .collect()
+    }
+
+    fn snapshot(&self, timestamp: f64) -> RhythmData {
+        let phases = self.phases();
+        let order = OrderParameter::of(&phases);
+
+        RhythmData {
+            rhythm_type: self.name().to_string(),
+            timestamp,
+            values: self.output_values(),
+            metadata: json!({
+                "phases": phases,
+                "unwrapped_phases": self.unwrapped_phases(),
+                "cycles": self.cycles,
+                "radii": self.radii,
+                "oscillator_model": self.model,
+                "bifurcation": self.params.bifurcation,
+                "coupling_strength": self.params.coupling_strength,
+                "topology": self.coupling.topology,
+                "max_delay": self.coupling.max_delay(),
+                "order_parameter": order.magnitude,
+                "mean_phase": order.phase,
+                "mean_order_parameter": self.sync.mean_order(),
+                "phase_locking": self.sync.phase_locking(),
+                "sync_state": self.sync_state(),
+                "time": self.time.as_secs_f64(),
+                "seed": self.seed,
+                "integration_scheme": self.integrator.scheme,
+            }),
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let mut state: Self = serde_json::from_value(state).map_err(|e| e.to_string())?;
+        let n = state.oscillators.len();
+        // States saved by older versions lack the coupling matrix, radii and cycle
+        // counts; their phases still hold the completed turns
+        if state.cycles.is_empty() {
+            state.cycles = state.oscillators.iter_mut().map(|osc| {
+                let (turns, phase) = wrap_phase(osc.phase);
+                osc.phase = phase;
+                turns
+            }).collect();
+        }
+        if state.coupling.size() == 0 {
+            state.coupling = CouplingMatrix::all_to_all(n);
+        }
+        if state.radii.is_empty() {
+            state.radii = vec![1.0; n];
+        }
+        if state.coupling.delays.is_empty() {
+            state.coupling.delays = vec![vec![0.0; n]; n];
+        }
+        // The history is only a cache of past states
+        if state.history.width() != n {
+            state.history.clear();
+        }
+        state.validate()?;
+        *self = state;
+        Ok(())
+    }
+
+    fn output_schema(&self) -> OutputSchema {
+        // Radius of the Stuart-Landau limit cycle, never below the unit start
+        let radius = match self.model {
+            OscillatorModel::PhaseOnly => 1.0,
+            OscillatorModel::StuartLandau => self.params.bifurcation.max(1.0).sqrt(),
+        };
+        let channels = self.oscillators
+            .iter()
+            .enumerate()
+            .map(|(i, osc)| {
+                ChannelSpec::new(
+                    &format!("oscillator_{}", i),
+                    &format!("Output of the {} Hz oscillator", osc.frequency),
+                    "",
+                    -osc.amplitude * radius,
+                    osc.amplitude * radius,
+                )
+            })
+            .collect();
+
+        OutputSchema {
+            channels,
+            metadata: vec![
+                MetadataField::new("phases", "Phase of each oscillator, in [0, 2π)", FieldType::NumberArray, "rad"),
+                MetadataField::new("unwrapped_phases", "Cumulative phase of each oscillator", FieldType::NumberArray, "rad"),
+                MetadataField::new("cycles", "Whole turns completed by each oscillator", FieldType::NumberArray, "cycles"),
+                MetadataField::new("radii", "Radius of each oscillator's complex amplitude", FieldType::NumberArray, ""),
+                MetadataField::new("oscillator_model", "phase_only or stuart_landau", FieldType::String, ""),
+                MetadataField::new("bifurcation", "Stuart-Landau bifurcation parameter", FieldType::Number, "1/s"),
+                MetadataField::new("coupling_strength", "Kuramoto coupling between oscillators", FieldType::Number, "rad/s"),
+                MetadataField::new("topology", "Generator of the coupling matrix, null if it was set directly", FieldType::Object, ""),
+                MetadataField::new("max_delay", "Longest coupling delay", FieldType::Number, "s"),
+                MetadataField::new("order_parameter", "Kuramoto order parameter magnitude r", FieldType::Number, ""),
+                MetadataField::new("mean_phase", "Kuramoto mean phase psi", FieldType::Number, "rad"),
+                MetadataField::new("mean_order_parameter", "Order parameter magnitude averaged over sync_window", FieldType::Number, ""),
+                MetadataField::new("phase_locking", "Pairwise phase-locking values over sync_window", FieldType::Matrix, ""),
+                MetadataField::new("sync_state", "incoherent, partial or synchronized", FieldType::String, ""),
+                MetadataField::new("time", "Simulated time since start", FieldType::Number, "s"),
+                MetadataField::seed(),
+                MetadataField::integration_scheme(),
+            ],
+        }
+    }
+
+    fn parameter_schema(&self) -> Vec<ParameterSpec> {
+        MultiTemporalParams::schema()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.params.to_json()
+    }
+
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.params = self.params.merged(update)?;
+        Ok(self.params())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_phase_handles_negative_phases() {
+        let (turns, phase) = wrap_phase(-0.5);
+        assert_eq!(turns, -1);
+        assert!((phase - (2.0 * PI - 0.5)).abs() < 1e-12);
+
+        let (turns, phase) = wrap_phase(-4.0 * PI - 1.0);
+        assert_eq!(turns, -3);
+        assert!((phase - (2.0 * PI - 1.0)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn wrap_phase_stays_below_two_pi() {
+        assert_eq!(wrap_phase(2.0 * PI), (1, 0.0));
+        assert_eq!(wrap_phase(0.0), (0, 0.0));
+        // Adding 2π to a tiny negative phase rounds to exactly 2π
+        let (turns, phase) = wrap_phase(-1e-17);
+        assert!((0.0..2.0 * PI).contains(&phase));
+        assert_eq!(2.0 * PI * turns as f64 + phase, 0.0);
+    }
+
+    #[test]
+    fn setting_a_phase_keeps_the_cycle_count() {
+        let mut rhythm = MultiTemporalRhythm::from_seed(1);
+        let update = OscillatorUpdate {
+            phase: Some(5.0 * PI),
+            ..OscillatorUpdate::default()
+        };
+        rhythm.update_oscillator(0, &update).unwrap();
+        assert_eq!(rhythm.cycles[0], 0);
+        assert!((rhythm.oscillators[0].phase - PI).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,175 @@
+use std::f64::consts::PI;
+use std::fs;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// One oscillator of a `MultiTemporalRhythm`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Oscillator {
+    /// Natural frequency, in Hz.
+    pub frequency: f64,
+    /// Phase, in rad.
+    pub phase: f64,
+    /// Peak output value.
+    pub amplitude: f64,
+    /// Scale of the random phase perturbation.
+    pub chaos_factor: f64,
+}
+
+impl Oscillator {
+    /// Highest natural frequency, kept well below the integrator step rate.
+    pub const MAX_FREQUENCY: f64 = 10.0;
+    pub const MAX_AMPLITUDE: f64 = 10.0;
+    pub const MAX_CHAOS_FACTOR: f64 = 10.0;
+
+    pub fn new(frequency: f64, phase: f64, amplitude: f64, chaos_factor: f64) -> Self {
+        Self {
+            frequency,
+            phase,
+            amplitude,
+            chaos_factor,
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if !(self.frequency > 0.0 && self.frequency <= Self::MAX_FREQUENCY) {
+            return Err(format!(
+                "frequency must be in (0, {}] Hz, got {}",
+                Self::MAX_FREQUENCY,
+                self.frequency
+            ));
+        }
+        if !self.phase.is_finite() {
+            return Err(format!("phase must be finite, got {}", self.phase));
+        }
+        if !(0.0..=Self::MAX_AMPLITUDE).contains(&self.amplitude) {
+            return Err(format!(
+                "amplitude must be in [0, {}], got {}",
+                Self::MAX_AMPLITUDE,
+                self.amplitude
+            ));
+        }
+        if !(0.0..=Self::MAX_CHAOS_FACTOR).contains(&self.chaos_factor) {
+            return Err(format!(
+                "chaos_factor must be in [0, {}], got {}",
+                Self::MAX_CHAOS_FACTOR,
+                self.chaos_factor
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Partial edit of an oscillator; fields left out keep their value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OscillatorUpdate {
+    pub frequency: Option<f64>,
+    pub phase: Option<f64>,
+    pub amplitude: Option<f64>,
+    pub chaos_factor: Option<f64>,
+}
+
+impl OscillatorUpdate {
+    /// `oscillator` with this update applied, validated as a whole.
+    pub fn apply(&self, oscillator: &Oscillator) -> Result<Oscillator, String> {
+        let updated = Oscillator {
+            frequency: self.frequency.unwrap_or(oscillator.frequency),
+            phase: self.phase.unwrap_or(oscillator.phase),
+            amplitude: self.amplitude.unwrap_or(oscillator.amplitude),
+            chaos_factor: self.chaos_factor.unwrap_or(oscillator.chaos_factor),
+        };
+        updated.validate()?;
+        Ok(updated)
+    }
+}
+
+/// Initial configuration of the oscillators of a `MultiTemporalRhythm`.
+///
+/// Stored as JSON of the form `{ "oscillators": [{ "frequency": 0.1, ... }] }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OscillatorBank {
+    pub oscillators: Vec<Oscillator>,
+}
+
+impl Default for OscillatorBank {
+    /// Three time scales, one decade apart.
+    fn default() -> Self {
+        Self {
+            oscillators: vec![
+                Oscillator::new(0.1, 0.0, 1.0, 0.3),
+                Oscillator::new(0.01, PI / 4.0, 0.8, 0.5),
+                Oscillator::new(0.001, PI / 2.0, 0.6, 0.7),
+            ],
+        }
+    }
+}
+
+impl OscillatorBank {
+    pub const MAX_OSCILLATORS: usize = 256;
+
+    pub fn new(oscillators: Vec<Oscillator>) -> Result<Self, String> {
+        let bank = Self { oscillators };
+        bank.validate()?;
+        Ok(bank)
+    }
+
+    /// `count` oscillators with frequencies spaced evenly on a log scale from
+    /// `highest_frequency` down to `lowest_frequency`.
+    ///
+    /// Slower oscillators get smaller amplitudes and more chaos, and phases
+    /// are staggered by π/4, following the default bank.
+    pub fn log_spaced(count: usize, highest_frequency: f64, lowest_frequency: f64) -> Result<Self, String> {
+        if !(lowest_frequency > 0.0 && lowest_frequency <= highest_frequency) {
+            return Err(format!(
+                "Frequencies must satisfy 0 < lowest <= highest, got {} and {}",
+                lowest_frequency, highest_frequency
+            ));
+        }
+
+        let ratio = lowest_frequency / highest_frequency;
+        let oscillators = (0..count)
+            .map(|i| {
+                let t = if count > 1 { i as f64 / (count - 1) as f64 } else { 0.0 };
+                Oscillator::new(
+                    highest_frequency * ratio.powf(t),
+                    (i as f64 * PI / 4.0) % (2.0 * PI),
+                    1.0 - 0.4 * t,
+                    0.3 + 0.4 * t,
+                )
+            })
+            .collect();
+
+        Self::new(oscillators)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        let count = self.oscillators.len();
+        if !(1..=Self::MAX_OSCILLATORS).contains(&count) {
+            return Err(format!(
+                "A bank must have between 1 and {} oscillators, got {}",
+                Self::MAX_OSCILLATORS,
+                count
+            ));
+        }
+        for (index, oscillator) in self.oscillators.iter().enumerate() {
+            oscillator
+                .validate()
+                .map_err(|e| format!("Oscillator {}: {}", index, e))?;
+        }
+        Ok(())
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| e.to_string())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, String> {
+        let json = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let bank: Self = serde_json::from_str(&json).map_err(|e| e.to_string())?;
+        bank.validate()?;
+        Ok(bank)
+    }
+}
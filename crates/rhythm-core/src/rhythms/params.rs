@@ -0,0 +1,103 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Describes a single tunable parameter of a rhythm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterSpec {
+    pub name: String,
+    pub description: String,
+    pub default: f64,
+    pub min: f64,
+    pub max: f64,
+    pub unit: String,
+}
+
+impl ParameterSpec {
+    pub fn new(name: &str, description: &str, default: f64, min: f64, max: f64, unit: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            default,
+            min,
+            max,
+            unit: unit.to_string(),
+        }
+    }
+
+    fn check(&self, value: &Value) -> Result<(), String> {
+        let number = value
+            .as_f64()
+            .ok_or_else(|| format!("{} must be a number, got {}", self.name, value))?;
+
+        if !(self.min..=self.max).contains(&number) {
+            let unit = if self.unit.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", self.unit)
+            };
+            return Err(format!(
+                "{} must be in [{}, {}]{}, got {}",
+                self.name, self.min, self.max, unit, number
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Typed parameter set of a rhythm.
+///
+/// Every numeric field must be described by `schema`, whose ranges are
+/// enforced whenever parameters are changed at runtime.
+pub trait RhythmParams: Serialize + DeserializeOwned + Default + Clone {
+    fn schema() -> Vec<ParameterSpec>;
+
+    /// Check cross-field constraints that a per-field range cannot express.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Check every field against its range and the cross-field constraints,
+    /// as for parameters read back from saved state.
+    fn check(&self) -> Result<(), String> {
+        let current = self.to_json();
+        for spec in Self::schema() {
+            if let Some(value) = current.get(&spec.name) {
+                spec.check(value)?;
+            }
+        }
+        self.validate()
+    }
+
+    fn to_json(&self) -> Value {
+        serde_json::to_value(self).expect("rhythm parameters serialize to JSON")
+    }
+
+    /// Return a copy with the fields in `update` replaced, after validation.
+    fn merged(&self, update: Value) -> Result<Self, String> {
+        let update = match update {
+            Value::Object(update) => update,
+            other => return Err(format!("Parameters must be a JSON object, got {}", other)),
+        };
+
+        let mut merged = match self.to_json() {
+            Value::Object(current) => current,
+            _ => unreachable!("rhythm parameters serialize to a JSON object"),
+        };
+
+        let schema = Self::schema();
+        for (name, value) in update {
+            if !merged.contains_key(&name) {
+                return Err(format!("Unknown parameter: {}", name));
+            }
+            if let Some(spec) = schema.iter().find(|spec| spec.name == name) {
+                spec.check(&value)?;
+            }
+            merged.insert(name, value);
+        }
+
+        let params: Self = serde_json::from_value(Value::Object(merged)).map_err(|e| e.to_string())?;
+        params.validate()?;
+        Ok(params)
+    }
+}
@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+/// Maximum-likelihood fit of a continuous power law `p(x) ∝ x^-α` for
+/// `x ≥ x_min`, after Clauset, Shalizi and Newman (2009).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PowerLawFit {
+    /// Exponent `α`.
+    pub exponent: f64,
+    /// Standard error of `α`.
+    pub exponent_error: f64,
+    /// Lower bound of the power-law tail, chosen to minimise `ks_distance`.
+    pub x_min: f64,
+    /// Number of samples in the tail.
+    pub tail_count: usize,
+    /// Kolmogorov-Smirnov distance between the tail and the fitted law.
+    pub ks_distance: f64,
+    /// Asymptotic Kolmogorov-Smirnov p-value. Because `α` and `x_min` are
+    /// fitted to the same data this overstates the fit; small values still
+    /// reliably reject a power law.
+    pub p_value: f64,
+}
+
+impl PowerLawFit {
+    /// Fewest tail samples a fit is attempted on.
+    pub const MIN_TAIL: usize = 10;
+    /// Most `x_min` candidates tried, spread evenly over the sorted samples.
+    const MAX_CANDIDATES: usize = 100;
+
+    /// Fit the positive values in `samples`, or `None` if there are too few.
+    pub fn fit(samples: &[f64]) -> Option<Self> {
+        let mut sorted: Vec<f64> = samples.iter().copied().filter(|x| *x > 0.0 && x.is_finite()).collect();
+        if sorted.len() < Self::MIN_TAIL {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+        // Suffix sums of ln x give each candidate tail's likelihood in O(1)
+        let mut log_sums = vec![0.0; sorted.len() + 1];
+        for i in (0..sorted.len()).rev() {
+            log_sums[i] = log_sums[i + 1] + sorted[i].ln();
+        }
+
+        let last_start = sorted.len() - Self::MIN_TAIL;
+        let stride = (last_start / Self::MAX_CANDIDATES).max(1);
+        let mut best: Option<Self> = None;
+
+        for start in (0..=last_start).step_by(stride) {
+            // Ties belong to the tail they start
+            if start > 0 && sorted[start - 1] == sorted[start] {
+                continue;
+            }
+            let x_min = sorted[start];
+            let tail = &sorted[start..];
+            let n = tail.len() as f64;
+            let log_excess = log_sums[start] - n * x_min.ln();
+            if log_excess <= 0.0 {
+                continue;
+            }
+            let exponent = 1.0 + n / log_excess;
+
+            let ks_distance = tail
+                .iter()
+                .enumerate()
+                .map(|(i, x)| {
+                    let model = 1.0 - (x / x_min).powf(1.0 - exponent);
+                    let below = i as f64 / n;
+                    let above = (i + 1) as f64 / n;
+                    (model - below).abs().max((above - model).abs())
+                })
+                .fold(0.0, f64::max);
+
+            if best.as_ref().map_or(true, |fit| ks_distance < fit.ks_distance) {
+                best = Some(Self {
+                    exponent,
+                    exponent_error: (exponent - 1.0) / n.sqrt(),
+                    x_min,
+                    tail_count: tail.len(),
+                    ks_distance,
+                    p_value: kolmogorov_p_value(ks_distance, tail.len()),
+                });
+            }
+        }
+
+        best
+    }
+}
+
+/// Probability of a Kolmogorov-Smirnov distance at least `distance` for `n`
+/// samples drawn from the hypothesised distribution.
+fn kolmogorov_p_value(distance: f64, n: usize) -> f64 {
+    let sqrt_n = (n as f64).sqrt();
+    let lambda = (sqrt_n + 0.12 + 0.11 / sqrt_n) * distance;
+    if lambda < 0.2 {
+        return 1.0;
+    }
+    let sum: f64 = (1..=100)
+        .map(|k| {
+            let k = k as f64;
+            let sign = if k as u64 % 2 == 1 { 1.0 } else { -1.0 };
+            sign * (-2.0 * k * k * lambda * lambda).exp()
+        })
+        .sum();
+    (2.0 * sum).clamp(0.0, 1.0)
+}
+
+/// One bin of a `LogHistogram`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistogramBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub count: u64,
+}
+
+/// Running histogram with logarithmically spaced bins, suited to heavy-tailed
+/// quantities. Values below `min` are counted in the first bin.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LogHistogram {
+    min: f64,
+    bins_per_decade: u32,
+    counts: Vec<u64>,
+}
+
+impl LogHistogram {
+    /// Upper limit on the number of bins; larger values land in the last one.
+    const MAX_BINS: usize = 100;
+
+    pub fn new(min: f64, bins_per_decade: u32) -> Self {
+        Self {
+            min,
+            bins_per_decade,
+            counts: Vec::new(),
+        }
+    }
+
+    pub fn add(&mut self, value: f64) {
+        let index = if value > self.min {
+            ((value / self.min).log10() * self.bins_per_decade as f64).floor() as usize
+        } else {
+            0
+        };
+        let index = index.min(Self::MAX_BINS - 1);
+        if index >= self.counts.len() {
+            self.counts.resize(index + 1, 0);
+        }
+        self.counts[index] += 1;
+    }
+
+    pub fn bins(&self) -> Vec<HistogramBin> {
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, &count)| HistogramBin {
+                lower: self.edge(i),
+                upper: self.edge(i + 1),
+                count,
+            })
+            .collect()
+    }
+
+    fn edge(&self, index: usize) -> f64 {
+        self.min * 10f64.powf(index as f64 / self.bins_per_decade as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::rng_from_seed;
+    use rand::Rng;
+
+    /// `count` draws from a Pareto law with exponent `exponent` above `x_min`.
+    fn pareto_samples(exponent: f64, x_min: f64, count: usize) -> Vec<f64> {
+        let mut rng = rng_from_seed(7);
+        (0..count)
+            .map(|_| x_min * (1.0 - rng.gen::<f64>()).powf(-1.0 / (exponent - 1.0)))
+            .collect()
+    }
+
+    #[test]
+    fn fit_recovers_pareto_exponent() {
+        let fit = PowerLawFit::fit(&pareto_samples(2.5, 1.0, 5000)).unwrap();
+        assert!(
+            (fit.exponent - 2.5).abs() < 3.0 * fit.exponent_error,
+            "exponent {} ± {}",
+            fit.exponent,
+            fit.exponent_error
+        );
+        assert!(fit.x_min < 2.0, "x_min {}", fit.x_min);
+        assert!(fit.p_value > 0.05, "p-value {}", fit.p_value);
+    }
+
+    #[test]
+    fn fit_needs_a_minimum_tail() {
+        let samples = pareto_samples(2.0, 1.0, PowerLawFit::MIN_TAIL - 1);
+        assert_eq!(PowerLawFit::fit(&samples), None);
+    }
+}
@@ -0,0 +1,484 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{ChannelSpec, FieldType, MetadataField, OutputSchema, ParameterSpec, Rhythm, RhythmParams};
+use super::hierarchy::{PredictiveLevel, TensionLevel};
+use super::predictor::{Predictor, PredictorConfig};
+use super::release::{ReleaseLog, ReleaseShape, ReleaseTransition};
+use super::signal::{SignalInput, SignalSource};
+use crate::{ElapsedTime, RhythmData};
+use crate::rng::{random_seed, rng_from_seed, RhythmRng};
+use rand::Rng;
+use std::any::Any;
+use std::f64::consts::{E, TAU};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PredictionTensionParams {
+    /// Number of prediction errors kept in the buffer of each level.
+    pub buffer_capacity: usize,
+    /// Tension level that triggers a release at any level.
+    pub release_threshold: f64,
+    /// Initial release intensity per unit of tension.
+    pub release_gain: f64,
+    /// Time in which a release's intensity falls by a factor `e`, in s.
+    pub release_decay_time: f64,
+    /// Time in which tension falls by a factor `e` during a release, in s.
+    pub tension_decay_time: f64,
+    /// Time after a release during which no other can start at the same
+    /// level, in s.
+    pub refractory_period: f64,
+    /// Time constant of the exponentially weighted error statistics, in s.
+    pub ewma_time_constant: f64,
+    /// Levels in the predictive hierarchy, the first included.
+    pub levels: usize,
+    /// Rate at which the second level's belief follows its evidence, in 1/s.
+    pub belief_rate: f64,
+    /// Factor by which each level is slower than the one below.
+    pub level_time_scale: f64,
+    /// Rate at which each level learns the variance of its errors, in 1/s.
+    pub precision_learning_rate: f64,
+}
+
+impl Default for PredictionTensionParams {
+    fn default() -> Self {
+        Self {
+            buffer_capacity: 100,
+            release_threshold: 0.8,
+            release_gain: 2.0,
+            // 0.95 and 0.9 per frame at 60 fps
+            release_decay_time: 0.325,
+            tension_decay_time: 0.158,
+            refractory_period: 0.0,
+            ewma_time_constant: 2.0,
+            levels: 1,
+            belief_rate: 1.0,
+            level_time_scale: 4.0,
+            precision_learning_rate: 0.5,
+        }
+    }
+}
+
+impl PredictionTensionParams {
+    pub const MAX_LEVELS: usize = 8;
+}
+
+impl RhythmParams for PredictionTensionParams {
+    fn schema() -> Vec<ParameterSpec> {
+        let defaults = Self::default();
+        vec![
+            ParameterSpec::new("buffer_capacity", "Number of prediction errors kept in the buffer", defaults.buffer_capacity as f64, 1.0, 1_000_000.0, "samples"),
+            ParameterSpec::new("release_threshold", "Tension level that triggers a release at any level", defaults.release_threshold, 0.0, 10.0, ""),
+            ParameterSpec::new("release_gain", "Initial release intensity per unit of tension", defaults.release_gain, 0.0, 10.0, ""),
+            ParameterSpec::new("release_decay_time", "Time in which a release's intensity falls by a factor e", defaults.release_decay_time, 0.001, 60.0, "s"),
+            ParameterSpec::new("tension_decay_time", "Time in which tension falls by a factor e during a release", defaults.tension_decay_time, 0.001, 60.0, "s"),
+            ParameterSpec::new("refractory_period", "Time after a release during which no other can start at the same level", defaults.refractory_period, 0.0, 600.0, "s"),
+            ParameterSpec::new("ewma_time_constant", "Time constant of the exponentially weighted error statistics", defaults.ewma_time_constant, 0.01, 3600.0, "s"),
+            ParameterSpec::new("levels", "Levels in the predictive hierarchy", defaults.levels as f64, 1.0, Self::MAX_LEVELS as f64, "levels"),
+            ParameterSpec::new("belief_rate", "Rate at which the second level's belief follows its evidence", defaults.belief_rate, 0.0, 100.0, "1/s"),
+            ParameterSpec::new("level_time_scale", "Factor by which each level is slower than the one below", defaults.level_time_scale, 1.0, 100.0, ""),
+            ParameterSpec::new("precision_learning_rate", "Rate at which each level learns the variance of its errors", defaults.precision_learning_rate, 0.0, 10.0, "1/s"),
+        ]
+    }
+}
+
+/// One prediction of the input signal and how it turned out.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PredictionStep {
+    pub observation: f64,
+    pub prediction: f64,
+    pub variance: f64,
+    pub error: f64,
+    pub precision: f64,
+    /// Precision-weighted squared error `π·ε²/2`; 1/2 on average when the
+    /// predicted variance is right.
+    pub surprise: f64,
+}
+
+/// Active-inference quantities of the latest update, in nats.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct InferenceMetrics {
+    /// Variational free energy of the whole hierarchy under the Laplace
+    /// approximation: `Σ π·ε²/2 + ln(2π/π)/2` over every level with an error.
+    pub free_energy: f64,
+    /// Shannon surprise `-ln p(x)` of the input sample under its prediction.
+    pub surprise: f64,
+    /// KL divergence of the predictor's updated belief from its prior.
+    pub bayesian_surprise: f64,
+    /// Expected free energy of the next sample: its ambiguity, the entropy
+    /// left once the hidden state is known, minus the information it is
+    /// expected to carry. No outcomes are preferred, so there is no risk
+    /// term.
+    pub expected_free_energy: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct PredictionTension {
+    /// First level; its errors are simulated without an input and are
+    /// precision-weighted surprises with one.
+    #[serde(flatten)]
+    base: TensionLevel,
+    params: PredictionTensionParams,
+    /// Levels above the first, lowest first.
+    #[serde(default)]
+    levels: Vec<PredictiveLevel>,
+    #[serde(default)]
+    input: SignalInput,
+    #[serde(default)]
+    predictor: Predictor,
+    /// Outcome of the latest prediction.
+    #[serde(default)]
+    last_step: Option<PredictionStep>,
+    #[serde(default)]
+    metrics: InferenceMetrics,
+    #[serde(default)]
+    release_shape: ReleaseShape,
+    #[serde(default)]
+    releases: ReleaseLog,
+    #[serde(default)]
+    time: ElapsedTime,
+    seed: u64,
+    rng: RhythmRng,
+}
+
+impl Default for PredictionTension {
+    fn default() -> Self {
+        Self::from_seed(random_seed())
+    }
+}
+
+impl PredictionTension {
+    /// Create the rhythm with all randomness drawn from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            base: TensionLevel::default(),
+            params: PredictionTensionParams::default(),
+            levels: Vec::new(),
+            input: SignalInput::default(),
+            predictor: Predictor::default(),
+            last_step: None,
+            metrics: InferenceMetrics::default(),
+            release_shape: ReleaseShape::default(),
+            releases: ReleaseLog::default(),
+            time: ElapsedTime::default(),
+            seed,
+            rng: rng_from_seed(seed),
+        }
+    }
+
+    pub fn input(&self) -> &SignalSource {
+        self.input.source()
+    }
+
+    /// Predict `source` instead of simulating errors, or go back to
+    /// simulation with `SignalSource::None`. The predictor and error buffer
+    /// start over.
+    pub fn set_input(&mut self, source: SignalSource) -> Result<(), String> {
+        if let SignalSource::Rhythm { id, .. } = &source {
+            if id == self.name() {
+                return Err(format!("{} cannot predict its own output", id));
+            }
+        }
+        self.input = SignalInput::new(source)?;
+        self.predictor.reset();
+        self.base.clear();
+        self.last_step = None;
+        Ok(())
+    }
+
+    pub fn predictor(&self) -> PredictorConfig {
+        self.predictor.config()
+    }
+
+    /// Replace the predictor with an untrained one.
+    pub fn set_predictor(&mut self, config: PredictorConfig) -> Result<(), String> {
+        config.validate()?;
+        self.predictor = Predictor::new(config);
+        self.last_step = None;
+        Ok(())
+    }
+
+    pub fn release_shape(&self) -> ReleaseShape {
+        self.release_shape
+    }
+
+    /// Shape releases that start from now on; one in progress keeps going
+    /// under the new shape.
+    pub fn set_release_shape(&mut self, shape: ReleaseShape) -> Result<(), String> {
+        shape.validate()?;
+        self.release_shape = shape;
+        Ok(())
+    }
+
+    /// Releases at every level since the rhythm was created, reseeded or
+    /// the log was cleared.
+    pub fn releases(&self) -> &ReleaseLog {
+        &self.releases
+    }
+
+    pub fn clear_releases(&mut self) {
+        self.releases.clear();
+    }
+
+    /// What the first level currently believes: the expected next input
+    /// sample, or its own tension when errors are simulated.
+    fn base_belief(&self) -> f64 {
+        match self.input.source() {
+            SignalSource::None => self.base.tension(),
+            _ => self.predictor.predict().mean,
+        }
+    }
+
+    pub fn metrics(&self) -> &InferenceMetrics {
+        &self.metrics
+    }
+
+    /// Let each higher level predict the belief below it, revise its own
+    /// belief and accumulate tension. A release resets the level's belief
+    /// to the evidence from below. Returns the levels' free energy.
+    fn update_hierarchy(&mut self, delta_time: f64) -> f64 {
+        let mut below = self.base_belief();
+        let mut surprises = Vec::with_capacity(self.levels.len());
+        let mut evidence = Vec::with_capacity(self.levels.len());
+        let mut free_energy = 0.0;
+        for level in &mut self.levels {
+            evidence.push(below);
+            let precision = level.precision();
+            let surprise = level.predict(below, self.params.precision_learning_rate, delta_time);
+            free_energy += surprise + 0.5 * (TAU / precision).ln();
+            surprises.push(surprise);
+            below = level.belief();
+        }
+
+        let predictions: Vec<(f64, f64)> = self.levels.iter().map(|level| (level.belief(), level.precision())).collect();
+        let mut rate = self.params.belief_rate;
+        let end_time = self.time.as_secs_f64() + delta_time;
+        for (i, level) in self.levels.iter_mut().enumerate() {
+            level.revise(evidence[i], predictions.get(i + 1).copied(), rate, delta_time);
+            rate /= self.params.level_time_scale;
+
+            let tension = level.tension_mut();
+            match tension.accumulate(Some(surprises[i]), delta_time, &self.params, &self.release_shape, &mut self.rng) {
+                ReleaseTransition::Started => level.reset_belief(evidence[i]),
+                ReleaseTransition::Ended(release) => self.releases.record(&release, i + 2, end_time),
+                ReleaseTransition::None => {}
+            }
+        }
+        free_energy
+    }
+
+    /// Predict the next input sample, learn from it and return the
+    /// precision-weighted surprise, or `None` without a sample.
+    fn predict_input(&mut self, delta_time: f64) -> Option<f64> {
+        let observation = self.input.sample(self.time.as_secs_f64() + delta_time, delta_time, &mut self.rng)?;
+        let prediction = self.predictor.predict();
+        let bayesian_surprise = self.predictor.observe(observation);
+
+        let error = observation - prediction.mean;
+        let precision = prediction.precision();
+        let surprise = 0.5 * precision * error * error;
+
+        // The next sample's variance splits into noise and what it reveals
+        let next = self.predictor.predict();
+        let information_gain = self.predictor.information_gain();
+        let noise = next.variance * (-2.0 * information_gain).exp();
+        self.metrics.surprise = surprise + 0.5 * (TAU * prediction.variance).ln();
+        self.metrics.bayesian_surprise = bayesian_surprise;
+        self.metrics.expected_free_energy = 0.5 * (TAU * E * noise).ln() - information_gain;
+
+        self.last_step = Some(PredictionStep {
+            observation,
+            prediction: prediction.mean,
+            variance: prediction.variance,
+            error,
+            precision,
+            surprise,
+        });
+        Some(surprise)
+    }
+}
+
+impl Rhythm for PredictionTension {
+    fn name(&self) -> &'static str {
+        "prediction_tension"
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        let params = self.params.clone();
+        let levels = self.levels.len();
+        let input = self.input.source().clone();
+        let predictor = self.predictor.config();
+        let release_shape = self.release_shape;
+        *self = Self::from_seed(seed);
+        self.release_shape = release_shape;
+        self.params = params;
+        self.input = SignalInput::new(input).unwrap_or_default();
+        self.predictor = Predictor::new(predictor);
+        self.levels = vec![PredictiveLevel::default(); levels];
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        self.metrics = InferenceMetrics::default();
+        let error = match self.input.source() {
+            // Simulate prediction errors
+            SignalSource::None => Some(self.rng.gen_range(0.0..0.3) * delta_time),
+            _ => self.predict_input(delta_time),
+        };
+        let transition = self.base.accumulate(error, delta_time, &self.params, &self.release_shape, &mut self.rng);
+        if let ReleaseTransition::Ended(release) = transition {
+            self.releases.record(&release, 1, self.time.as_secs_f64() + delta_time);
+        }
+        self.metrics.free_energy = self.metrics.surprise + self.update_hierarchy(delta_time);
+        self.time.advance(delta_time);
+    }
+
+    fn output_values(&self) -> Vec<f64> {
+        let mut values = vec![
+            self.base.tension(),
+            self.base.release_intensity(),
+            self.metrics.free_energy,
+            self.metrics.surprise,
+            self.metrics.bayesian_surprise,
+            self.metrics.expected_free_energy,
+        ];
+        for level in &self.levels {
+            values.push(level.tension().tension());
+            values.push(level.tension().release_intensity());
+        }
+        values
+    }
+
+    fn snapshot(&self, timestamp: f64) -> RhythmData {
+        let levels: Vec<_> = self.levels
+            .iter()
+            .map(|level| json!({
+                "belief": level.belief(),
+                "error": level.error(),
+                "precision": level.precision(),
+                "release_active": level.tension().release_active(),
+                "refractory": level.tension().refractory(),
+            }))
+            .collect();
+
+        RhythmData {
+            rhythm_type: self.name().to_string(),
+            timestamp,
+            values: self.output_values(),
+            metadata: json!({
+                "release_active": self.base.release_active(),
+                "refractory": self.base.refractory(),
+                "release_shape": self.release_shape,
+                "release_count": self.releases.count(),
+                "last_release": self.releases.last(),
+                "buffer_size": self.base.buffer_size(),
+                "variance": self.base.variance(),
+                "ewma_mean": self.base.ewma().mean(),
+                "ewma_variance": self.base.ewma().variance(),
+                "levels": levels,
+                "input_source": self.input.source(),
+                "prediction": self.last_step,
+                "seed": self.seed,
+            }),
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let mut state: Self = serde_json::from_value(state).map_err(|e| e.to_string())?;
+        state.params.check()?;
+        state.input.source().validate()?;
+        state.predictor.validate()?;
+        state.release_shape.validate()?;
+        state.levels.resize_with(state.params.levels - 1, PredictiveLevel::default);
+        *self = state;
+        Ok(())
+    }
+
+    fn output_schema(&self) -> OutputSchema {
+        let mut channels = vec![
+            ChannelSpec::new("tension", "Accumulated prediction error", "", 0.0, 1.0),
+            ChannelSpec::new("release_intensity", "Strength of the current release", "", 0.0, 2.0),
+            ChannelSpec::new("free_energy", "Variational free energy of the hierarchy", "nats", -20.0, 20.0),
+            ChannelSpec::new("surprise", "Shannon surprise of the input sample; zero without an input", "nats", -5.0, 10.0),
+            ChannelSpec::new("bayesian_surprise", "KL divergence of the predictor's belief update; zero without an input", "nats", 0.0, 5.0),
+            ChannelSpec::new("expected_free_energy", "Ambiguity minus expected information gain of the next sample; zero without an input", "nats", -5.0, 10.0),
+        ];
+        for level in 2..=self.levels.len() + 1 {
+            channels.push(ChannelSpec::new(
+                &format!("tension_{}", level),
+                &format!("Accumulated precision-weighted error of hierarchy level {}", level),
+                "",
+                0.0,
+                1.0,
+            ));
+            channels.push(ChannelSpec::new(
+                &format!("release_intensity_{}", level),
+                &format!("Strength of the current release at level {}", level),
+                "",
+                0.0,
+                2.0,
+            ));
+        }
+
+        OutputSchema {
+            channels,
+            metadata: vec![
+                MetadataField::new("release_active", "Whether a release is in progress", FieldType::Boolean, ""),
+                MetadataField::new("refractory", "Time left before another release may start", FieldType::Number, "s"),
+                MetadataField::new("release_shape", "Time course of releases", FieldType::Object, ""),
+                MetadataField::new("release_count", "Releases at every level since the log was cleared", FieldType::Integer, ""),
+                MetadataField::new("last_release", "level, start_time, duration, peak and pre_release_tension of the latest release, or null", FieldType::Object, ""),
+                MetadataField::new("buffer_size", "Prediction errors currently buffered", FieldType::Integer, "samples"),
+                MetadataField::new("variance", "Variance of the buffered errors", FieldType::Number, ""),
+                MetadataField::new("ewma_mean", "Exponentially weighted mean of the errors", FieldType::Number, ""),
+                MetadataField::new("ewma_variance", "Exponentially weighted variance of the errors", FieldType::Number, ""),
+                MetadataField::new("levels", "belief, error, precision, release_active and refractory of each level above the first", FieldType::Object, ""),
+                MetadataField::new("input_source", "Signal being predicted; kind none means errors are simulated", FieldType::Object, ""),
+                MetadataField::new("prediction", "observation, prediction, variance, error, precision and surprise of the latest prediction, or null", FieldType::Object, ""),
+                MetadataField::seed(),
+            ],
+        }
+    }
+
+    fn parameter_schema(&self) -> Vec<ParameterSpec> {
+        PredictionTensionParams::schema()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.params.to_json()
+    }
+
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.params = self.params.merged(update)?;
+
+        // Drop the oldest errors if the buffers were shrunk
+        self.base.truncate(self.params.buffer_capacity);
+        for level in &mut self.levels {
+            level.tension_mut().truncate(self.params.buffer_capacity);
+        }
+        self.levels.resize_with(self.params.levels - 1, PredictiveLevel::default);
+
+        Ok(self.params())
+    }
+
+    fn input_source(&self) -> Option<(String, usize)> {
+        match self.input.source() {
+            SignalSource::Rhythm { id, channel } => Some((id.clone(), *channel)),
+            _ => None,
+        }
+    }
+
+    fn receive_input(&mut self, value: f64) {
+        self.input.receive(value);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
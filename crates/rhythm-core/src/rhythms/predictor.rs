@@ -0,0 +1,320 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Predictor choice and its hyperparameters.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PredictorConfig {
+    /// Autoregressive model of order `order`, fitted online by recursive
+    /// least squares; `forgetting` in (0, 1] sets how quickly old samples
+    /// lose weight.
+    Autoregressive { order: usize, forgetting: f64 },
+    /// Kalman filter for a random-walk level observed through noise.
+    Kalman { process_noise: f64, measurement_noise: f64 },
+}
+
+impl Default for PredictorConfig {
+    fn default() -> Self {
+        PredictorConfig::Autoregressive {
+            order: 4,
+            forgetting: 0.99,
+        }
+    }
+}
+
+impl PredictorConfig {
+    pub const MAX_ORDER: usize = 32;
+
+    pub fn validate(&self) -> Result<(), String> {
+        match *self {
+            PredictorConfig::Autoregressive { order, forgetting } => {
+                if !(1..=Self::MAX_ORDER).contains(&order) {
+                    return Err(format!("order must be in [1, {}], got {}", Self::MAX_ORDER, order));
+                }
+                if !(forgetting > 0.0 && forgetting <= 1.0) {
+                    return Err(format!("forgetting must be in (0, 1], got {}", forgetting));
+                }
+            }
+            PredictorConfig::Kalman { process_noise, measurement_noise } => {
+                if !(process_noise >= 0.0 && process_noise.is_finite()) {
+                    return Err(format!("process_noise must not be negative, got {}", process_noise));
+                }
+                if !(measurement_noise > 0.0 && measurement_noise.is_finite()) {
+                    return Err(format!("measurement_noise must be positive, got {}", measurement_noise));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Gaussian prediction of the next sample.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Prediction {
+    pub mean: f64,
+    pub variance: f64,
+}
+
+impl Prediction {
+    pub fn precision(&self) -> f64 {
+        1.0 / self.variance
+    }
+}
+
+/// Variance below which predictions are not trusted further.
+const MIN_VARIANCE: f64 = 1e-9;
+
+/// Online predictor of a scalar signal, one sample at a time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Predictor {
+    Autoregressive {
+        order: usize,
+        forgetting: f64,
+        /// Bias followed by one coefficient per lag.
+        coefficients: Vec<f64>,
+        /// Inverse information matrix of the least-squares fit.
+        covariance: Vec<Vec<f64>>,
+        /// Latest samples, newest first.
+        history: VecDeque<f64>,
+        /// Running mean squared prediction error.
+        error_variance: f64,
+    },
+    Kalman {
+        process_noise: f64,
+        measurement_noise: f64,
+        level: f64,
+        /// Variance of the level estimate.
+        variance: f64,
+    },
+}
+
+impl Default for Predictor {
+    fn default() -> Self {
+        Self::new(PredictorConfig::default())
+    }
+}
+
+impl Predictor {
+    /// Initial variance of every least-squares coefficient; large, so early
+    /// samples dominate the prior.
+    const PRIOR_VARIANCE: f64 = 100.0;
+
+    pub fn new(config: PredictorConfig) -> Self {
+        match config {
+            PredictorConfig::Autoregressive { order, forgetting } => {
+                let size = order + 1;
+                Predictor::Autoregressive {
+                    order,
+                    forgetting,
+                    coefficients: vec![0.0; size],
+                    covariance: (0..size)
+                        .map(|i| (0..size).map(|j| if i == j { Self::PRIOR_VARIANCE } else { 0.0 }).collect())
+                        .collect(),
+                    history: VecDeque::from(vec![0.0; order]),
+                    error_variance: 1.0,
+                }
+            }
+            PredictorConfig::Kalman { process_noise, measurement_noise } => Predictor::Kalman {
+                process_noise,
+                measurement_noise,
+                level: 0.0,
+                variance: 1.0,
+            },
+        }
+    }
+
+    pub fn config(&self) -> PredictorConfig {
+        match *self {
+            Predictor::Autoregressive { order, forgetting, .. } => PredictorConfig::Autoregressive { order, forgetting },
+            Predictor::Kalman { process_noise, measurement_noise, .. } => {
+                PredictorConfig::Kalman { process_noise, measurement_noise }
+            }
+        }
+    }
+
+    /// Check a predictor read back from saved state.
+    pub fn validate(&self) -> Result<(), String> {
+        self.config().validate()?;
+        if let Predictor::Autoregressive { order, coefficients, covariance, history, .. } = self {
+            let size = order + 1;
+            if coefficients.len() != size
+                || history.len() != *order
+                || covariance.len() != size
+                || covariance.iter().any(|row| row.len() != size)
+            {
+                return Err(format!("Autoregressive state does not match order {}", order));
+            }
+        }
+        Ok(())
+    }
+
+    /// Forget everything learned, keeping the configuration.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.config());
+    }
+
+    /// Prediction of the next sample.
+    pub fn predict(&self) -> Prediction {
+        let (mean, variance) = match self {
+            Predictor::Autoregressive { coefficients, history, error_variance, .. } => {
+                (dot(coefficients, &regressors(history)), *error_variance)
+            }
+            Predictor::Kalman { process_noise, measurement_noise, level, variance } => {
+                (*level, variance + process_noise + measurement_noise)
+            }
+        };
+        Prediction {
+            mean,
+            variance: variance.max(MIN_VARIANCE),
+        }
+    }
+
+    /// Information the next sample is expected to carry about the hidden
+    /// state, in nats: the expected Bayesian surprise of `observe`.
+    pub fn information_gain(&self) -> f64 {
+        match self {
+            Predictor::Autoregressive { forgetting, covariance, history, .. } => {
+                let phi = regressors(history);
+                let spread: f64 = covariance.iter().zip(&phi).map(|(row, x)| dot(row, &phi) * x).sum();
+                0.5 * (1.0 + spread / *forgetting).ln()
+            }
+            Predictor::Kalman { process_noise, measurement_noise, variance, .. } => {
+                0.5 * ((variance + process_noise + measurement_noise) / *measurement_noise).ln()
+            }
+        }
+    }
+
+    /// Learn from the sample that actually arrived and return the Bayesian
+    /// surprise, the KL divergence of the updated belief about the hidden
+    /// state (coefficients or level) from the prior one, in nats.
+    pub fn observe(&mut self, value: f64) -> f64 {
+        match self {
+            Predictor::Autoregressive {
+                forgetting,
+                coefficients,
+                covariance,
+                history,
+                error_variance,
+                ..
+            } => {
+                let phi = regressors(history);
+                let error = value - dot(coefficients, &phi);
+
+                // Recursive least squares with exponential forgetting
+                let p_phi: Vec<f64> = covariance.iter().map(|row| dot(row, &phi)).collect();
+                let spread = dot(&phi, &p_phi);
+                let denominator = *forgetting + spread;
+                // Coefficients ~ N(w, σ²P) with noise variance σ²λ, where the
+                // predicted variance is σ²(λ + φᵀPφ)
+                let bayesian_surprise = 0.5
+                    * ((1.0 + spread / *forgetting).ln() - spread / denominator
+                        + error * error * spread / (error_variance.max(MIN_VARIANCE) * denominator));
+
+                let gain: Vec<f64> = p_phi.iter().map(|x| x / denominator).collect();
+                for (coefficient, k) in coefficients.iter_mut().zip(&gain) {
+                    *coefficient += k * error;
+                }
+                for (row, k) in covariance.iter_mut().zip(&gain) {
+                    for (entry, p) in row.iter_mut().zip(&p_phi) {
+                        *entry = (*entry - k * p) / *forgetting;
+                    }
+                }
+                // Forgetting inflates the covariance along directions the signal
+                // does not excite; keep it symmetric and its trace within the prior
+                let size = covariance.len();
+                *covariance = (0..size)
+                    .map(|i| (0..size).map(|j| 0.5 * (covariance[i][j] + covariance[j][i])).collect())
+                    .collect();
+                let trace: f64 = covariance.iter().enumerate().map(|(i, row)| row[i]).sum();
+                let limit = Self::PRIOR_VARIANCE * size as f64;
+                if trace > limit {
+                    for entry in covariance.iter_mut().flatten() {
+                        *entry *= limit / trace;
+                    }
+                }
+
+                // Averaged over the same horizon as the fit, but never more than 100 samples
+                let weight = (1.0 - *forgetting).max(0.01);
+                *error_variance += weight * (error * error - *error_variance);
+                history.pop_back();
+                history.push_front(value);
+                bayesian_surprise
+            }
+            Predictor::Kalman { process_noise, measurement_noise, level, variance } => {
+                let prior = *variance + *process_noise;
+                let gain = prior / (prior + *measurement_noise);
+                let error = value - *level;
+                *level += gain * error;
+                *variance = (1.0 - gain) * prior;
+                0.5 * (gain * gain * error * error / prior - gain - (1.0 - gain).ln())
+            }
+        }
+    }
+}
+
+/// Bias term followed by the lagged samples.
+fn regressors(history: &VecDeque<f64>) -> Vec<f64> {
+    std::iter::once(1.0).chain(history.iter().copied()).collect()
+}
+
+fn dot(a: &[f64], b: &[f64]) -> f64 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::{rng_from_seed, standard_normal};
+
+    #[test]
+    fn autoregressive_fit_recovers_ar2_coefficients() {
+        let expected = [0.3, 0.6, -0.3];
+        let mut predictor = Predictor::new(PredictorConfig::Autoregressive { order: 2, forgetting: 1.0 });
+        let mut rng = rng_from_seed(11);
+        let mut lags = [0.0, 0.0];
+        for _ in 0..5000 {
+            let value = expected[0] + expected[1] * lags[0] + expected[2] * lags[1] + 0.1 * standard_normal(&mut rng);
+            predictor.observe(value);
+            lags = [value, lags[0]];
+        }
+
+        match &predictor {
+            Predictor::Autoregressive { coefficients, error_variance, .. } => {
+                for (coefficient, expected) in coefficients.iter().zip(&expected) {
+                    assert!((coefficient - expected).abs() < 0.02, "{:?}", coefficients);
+                }
+                assert!((error_variance - 0.01).abs() < 0.005, "error variance {}", error_variance);
+            }
+            Predictor::Kalman { .. } => unreachable!(),
+        }
+    }
+
+    /// KL divergence of `N(mean, variance)` from `N(prior_mean, prior_variance)`.
+    fn gaussian_kl(mean: f64, variance: f64, prior_mean: f64, prior_variance: f64) -> f64 {
+        0.5 * (variance / prior_variance + (mean - prior_mean).powi(2) / prior_variance - 1.0
+            + (prior_variance / variance).ln())
+    }
+
+    #[test]
+    fn kalman_surprise_is_the_kl_divergence_of_the_update() {
+        let mut predictor = Predictor::new(PredictorConfig::Kalman {
+            process_noise: 0.05,
+            measurement_noise: 0.2,
+        });
+        for value in [1.0, -0.5, 2.0, 0.3, 0.3] {
+            let (prior_mean, prior_variance) = match predictor {
+                Predictor::Kalman { level, variance, process_noise, .. } => (level, variance + process_noise),
+                Predictor::Autoregressive { .. } => unreachable!(),
+            };
+            let surprise = predictor.observe(value);
+            let (mean, variance) = match predictor {
+                Predictor::Kalman { level, variance, .. } => (level, variance),
+                Predictor::Autoregressive { .. } => unreachable!(),
+            };
+            let expected = gaussian_kl(mean, variance, prior_mean, prior_variance);
+            assert!((surprise - expected).abs() < 1e-12, "{} vs {}", surprise, expected);
+        }
+    }
+}
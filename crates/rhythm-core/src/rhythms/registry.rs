@@ -0,0 +1,184 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use super::*;
+use crate::rng::{derive_seed, random_seed};
+
+pub type SharedRhythm = Arc<Mutex<Box<dyn Rhythm>>>;
+
+/// Rhythms addressable by id.
+///
+/// Every registered rhythm draws its randomness from a stream derived from
+/// the registry's master seed, so a whole session can be replayed from one
+/// number.
+pub struct RhythmRegistry {
+    rhythms: BTreeMap<String, SharedRhythm>,
+    master_seed: AtomicU64,
+}
+
+impl Default for RhythmRegistry {
+    fn default() -> Self {
+        Self::with_seed(random_seed())
+    }
+}
+
+impl RhythmRegistry {
+    pub fn empty(master_seed: u64) -> Self {
+        Self {
+            rhythms: BTreeMap::new(),
+            master_seed: AtomicU64::new(master_seed),
+        }
+    }
+
+    /// Registry holding every built-in rhythm, seeded from `master_seed`.
+    pub fn with_seed(master_seed: u64) -> Self {
+        let mut registry = Self::empty(master_seed);
+        registry.register(MultiTemporalRhythm::default());
+        registry.register(CriticalPhi::default());
+        registry.register(PredictionTension::default());
+        registry.register(SemanticVortex::default());
+        registry.register(AttentionWandering::default());
+        registry
+    }
+
+    /// Register a rhythm under its own name, replacing any previous entry.
+    ///
+    /// The rhythm is reseeded from the master seed on registration.
+    pub fn register<R: Rhythm + 'static>(&mut self, mut rhythm: R) {
+        let id = rhythm.name().to_string();
+        rhythm.reseed(derive_seed(self.master_seed(), &id));
+        self.rhythms.insert(id, Arc::new(Mutex::new(Box::new(rhythm))));
+    }
+
+    pub fn get(&self, id: &str) -> Result<SharedRhythm, String> {
+        self.rhythms
+            .get(id)
+            .cloned()
+            .ok_or_else(|| format!("Unknown rhythm type: {}", id))
+    }
+
+    /// Run `f` on the rhythm registered as `id`, which must be of type `R`.
+    pub fn with_rhythm<R: Rhythm + 'static, T>(
+        &self,
+        id: &str,
+        f: impl FnOnce(&mut R) -> Result<T, String>,
+    ) -> Result<T, String> {
+        let rhythm = self.get(id)?;
+        let mut rhythm = rhythm.lock().unwrap();
+        match rhythm.as_any_mut().downcast_mut::<R>() {
+            Some(rhythm) => f(rhythm),
+            None => {
+                let type_name = std::any::type_name::<R>().rsplit("::").next().unwrap_or_default();
+                Err(format!("Rhythm {} is not a {}", id, type_name))
+            }
+        }
+    }
+
+    pub fn ids(&self) -> Vec<String> {
+        self.rhythms.keys().cloned().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &SharedRhythm)> {
+        self.rhythms.iter()
+    }
+
+    /// Advance every rhythm by `delta_time` seconds.
+    ///
+    /// Inputs are read before any rhythm moves, so every rhythm sees its
+    /// source as it was at the start of the tick, whatever the update order.
+    pub fn update_all(&self, delta_time: f64) {
+        let sources: Vec<(&SharedRhythm, String, usize)> = self
+            .rhythms
+            .values()
+            .filter_map(|rhythm| {
+                let (source, channel) = rhythm.lock().unwrap().input_source()?;
+                Some((rhythm, source, channel))
+            })
+            .collect();
+        let inputs: Vec<(&SharedRhythm, f64)> = sources
+            .into_iter()
+            .filter_map(|(rhythm, source, channel)| {
+                let source = self.rhythms.get(&source)?;
+                let value = *source.lock().unwrap().output_values().get(channel)?;
+                Some((rhythm, value))
+            })
+            .collect();
+        for (rhythm, value) in inputs {
+            rhythm.lock().unwrap().receive_input(value);
+        }
+
+        for rhythm in self.rhythms.values() {
+            rhythm.lock().unwrap().update(delta_time);
+        }
+    }
+
+    pub fn master_seed(&self) -> u64 {
+        self.master_seed.load(Ordering::SeqCst)
+    }
+
+    /// Record `master_seed` without touching any rhythm, e.g. after restoring
+    /// rhythm states that were derived from it.
+    pub fn set_master_seed(&self, master_seed: u64) {
+        self.master_seed.store(master_seed, Ordering::SeqCst);
+    }
+
+    /// Reset every rhythm from a new master seed.
+    pub fn reseed(&self, master_seed: u64) {
+        self.master_seed.store(master_seed, Ordering::SeqCst);
+        for (id, rhythm) in &self.rhythms {
+            rhythm.lock().unwrap().reseed(derive_seed(master_seed, id));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Values, as bits, and metadata of every rhythm after each of `ticks` updates.
+    fn run(registry: &RhythmRegistry, ticks: usize) -> Vec<(Vec<u64>, serde_json::Value)> {
+        let mut frames = Vec::new();
+        for _ in 0..ticks {
+            registry.update_all(1.0 / 60.0);
+            for (_, rhythm) in registry.iter() {
+                let frame = rhythm.lock().unwrap().snapshot(0.0);
+                frames.push((frame.values.iter().map(|value| value.to_bits()).collect(), frame.metadata));
+            }
+        }
+        frames
+    }
+
+    #[test]
+    fn same_master_seed_gives_same_frames() {
+        let first = run(&RhythmRegistry::with_seed(1234), 300);
+        let second = run(&RhythmRegistry::with_seed(1234), 300);
+        assert!(first == second);
+        assert!(first != run(&RhythmRegistry::with_seed(1235), 300));
+    }
+
+    #[test]
+    fn reseed_reproduces_the_original_stream() {
+        let registry = RhythmRegistry::with_seed(99);
+        let original = run(&registry, 100);
+        registry.reseed(99);
+        assert!(run(&registry, 100) == original);
+
+        let rhythm = registry.get("critical_phi").unwrap();
+        let mut rhythm = rhythm.lock().unwrap();
+        let seed = derive_seed(99, "critical_phi");
+        let values = |rhythm: &mut Box<dyn Rhythm>| -> Vec<u64> {
+            rhythm.reseed(seed);
+            (0..100)
+                .flat_map(|_| {
+                    rhythm.update(1.0 / 60.0);
+                    rhythm.snapshot(0.0).values
+                })
+                .map(f64::to_bits)
+                .collect()
+        };
+        let first = values(&mut rhythm);
+        assert_eq!(rhythm.seed(), seed);
+        assert_eq!(values(&mut rhythm), first);
+    }
+}
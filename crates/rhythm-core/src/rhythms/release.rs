@@ -0,0 +1,212 @@
+use std::collections::VecDeque;
+use std::f64::consts::TAU;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Time course of a release. Every shape fades under the same exponential
+/// envelope, which starts at the release's initial intensity and ends the
+/// release once it falls below 0.01.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ReleaseShape {
+    /// The envelope itself.
+    Exponential,
+    /// The envelope modulated by a raised cosine of `frequency` Hz, so the
+    /// release comes in waves.
+    Oscillatory { frequency: f64 },
+    /// Pulses arriving at random at `rate` per second, each starting at the
+    /// envelope and fading with time constant `pulse_time` seconds.
+    Burst { rate: f64, pulse_time: f64 },
+}
+
+impl Default for ReleaseShape {
+    fn default() -> Self {
+        ReleaseShape::Exponential
+    }
+}
+
+impl ReleaseShape {
+    pub fn validate(&self) -> Result<(), String> {
+        let (name, value) = match *self {
+            ReleaseShape::Exponential => return Ok(()),
+            ReleaseShape::Oscillatory { frequency } => ("frequency", frequency),
+            ReleaseShape::Burst { rate, .. } if !(rate > 0.0 && rate.is_finite()) => ("rate", rate),
+            ReleaseShape::Burst { pulse_time, .. } => ("pulse_time", pulse_time),
+        };
+        if value > 0.0 && value.is_finite() {
+            Ok(())
+        } else {
+            Err(format!("{} must be positive, got {}", name, value))
+        }
+    }
+
+    /// Fraction of the envelope released `elapsed` seconds into a release,
+    /// in [0, 1]. `pulse` carries the burst state between steps.
+    fn modulation(&self, elapsed: f64, pulse: &mut f64, delta_time: f64, rng: &mut impl Rng) -> f64 {
+        match *self {
+            ReleaseShape::Exponential => 1.0,
+            ReleaseShape::Oscillatory { frequency } => 0.5 + 0.5 * (TAU * frequency * elapsed).cos(),
+            ReleaseShape::Burst { rate, pulse_time } => {
+                *pulse *= (-delta_time / pulse_time).exp();
+                if rng.gen_bool(1.0 - (-rate * delta_time).exp()) {
+                    *pulse = 1.0;
+                }
+                *pulse
+            }
+        }
+    }
+}
+
+/// Progress of the release in progress at one level.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseState {
+    elapsed: f64,
+    envelope: f64,
+    pulse: f64,
+    peak: f64,
+    pre_release_tension: f64,
+}
+
+impl ReleaseState {
+    /// Start a release of initial intensity `intensity` from `tension`.
+    pub fn new(intensity: f64, tension: f64) -> Self {
+        Self {
+            elapsed: 0.0,
+            envelope: intensity,
+            pulse: 1.0,
+            peak: intensity,
+            pre_release_tension: tension,
+        }
+    }
+
+    pub fn elapsed(&self) -> f64 {
+        self.elapsed
+    }
+
+    /// Whether the envelope has faded out.
+    pub fn finished(&self) -> bool {
+        self.envelope < 0.01
+    }
+
+    /// Advance by `delta_time`, with the envelope fading by `e` every
+    /// `decay_time` seconds, and return the new intensity.
+    pub fn step(&mut self, shape: &ReleaseShape, decay_time: f64, delta_time: f64, rng: &mut impl Rng) -> f64 {
+        self.elapsed += delta_time;
+        self.envelope *= (-delta_time / decay_time).exp();
+        let intensity = self.envelope * shape.modulation(self.elapsed, &mut self.pulse, delta_time, rng);
+        self.peak = self.peak.max(intensity);
+        intensity
+    }
+}
+
+/// What a step did to a level's release.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReleaseTransition {
+    None,
+    Started,
+    /// A release ended, leaving its final state.
+    Ended(ReleaseState),
+}
+
+/// One completed release.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseEvent {
+    /// Hierarchy level, 1 for the first.
+    pub level: usize,
+    /// Simulated time the release started, in seconds.
+    pub start_time: f64,
+    pub duration: f64,
+    /// Largest intensity reached.
+    pub peak: f64,
+    /// Tension that triggered the release.
+    pub pre_release_tension: f64,
+}
+
+/// Log of the most recent releases at every level.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReleaseLog {
+    events: VecDeque<ReleaseEvent>,
+    count: u64,
+}
+
+impl ReleaseLog {
+    /// Most releases kept.
+    pub const CAPACITY: usize = 1000;
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Releases recorded since the log was cleared.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The most recent `limit` releases, oldest first.
+    pub fn recent(&self, limit: usize) -> Vec<ReleaseEvent> {
+        let skip = self.events.len().saturating_sub(limit);
+        self.events.iter().skip(skip).copied().collect()
+    }
+
+    pub fn last(&self) -> Option<&ReleaseEvent> {
+        self.events.back()
+    }
+
+    /// Record a release of `level` that ended at `end_time` in `state`.
+    pub fn record(&mut self, state: &ReleaseState, level: usize, end_time: f64) {
+        let event = ReleaseEvent {
+            level,
+            start_time: end_time - state.elapsed,
+            duration: state.elapsed,
+            peak: state.peak,
+            pre_release_tension: state.pre_release_tension,
+        };
+        self.count += 1;
+        self.events.push_back(event);
+        if self.events.len() > Self::CAPACITY {
+            self.events.pop_front();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rng::rng_from_seed;
+    use crate::rhythms::prediction_tension::PredictionTensionParams;
+
+    #[test]
+    fn default_decay_keeps_old_per_frame_factor() {
+        let params = PredictionTensionParams::default();
+        let mut rng = rng_from_seed(0);
+        let mut release = ReleaseState::new(1.0, 1.0);
+        let mut intensity = 1.0;
+        for _ in 0..10 {
+            let next = release.step(&ReleaseShape::Exponential, params.release_decay_time, 1.0 / 60.0, &mut rng);
+            assert!((next / intensity - 0.95).abs() < 1e-3, "factor {}", next / intensity);
+            intensity = next;
+        }
+    }
+
+    #[test]
+    fn shapes_stay_under_the_envelope() {
+        let shapes = [
+            ReleaseShape::Exponential,
+            ReleaseShape::Oscillatory { frequency: 3.0 },
+            ReleaseShape::Burst { rate: 5.0, pulse_time: 0.05 },
+        ];
+        for shape in &shapes {
+            let mut rng = rng_from_seed(1);
+            let mut release = ReleaseState::new(2.0, 1.0);
+            let mut steps = 0;
+            while !release.finished() {
+                let intensity = release.step(shape, 0.3, 1.0 / 60.0, &mut rng);
+                assert!((0.0..=release.envelope).contains(&intensity), "{:?}", shape);
+                steps += 1;
+            }
+            // The envelope alone sets the duration: 0.3 · ln(2 / 0.01) s
+            assert_eq!(steps, (0.3 * 200f64.ln() * 60.0).ceil() as usize, "{:?}", shape);
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+
+use super::ParameterSpec;
+use crate::integrator::IntegrationScheme;
+
+/// Describes one entry of `RhythmData.values`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelSpec {
+    pub name: String,
+    pub description: String,
+    pub unit: String,
+    /// Expected range; values may briefly leave it.
+    pub min: f64,
+    pub max: f64,
+}
+
+impl ChannelSpec {
+    pub fn new(name: &str, description: &str, unit: &str, min: f64, max: f64) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            unit: unit.to_string(),
+            min,
+            max,
+        }
+    }
+}
+
+/// JSON shape of a metadata field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    Number,
+    Integer,
+    Boolean,
+    String,
+    /// Array of numbers.
+    NumberArray,
+    /// Array of `[x, y]` pairs.
+    PointArray,
+    /// Array of rows of numbers.
+    Matrix,
+    /// Object with arbitrary keys.
+    Object,
+}
+
+/// Describes one key of `RhythmData.metadata`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetadataField {
+    pub name: String,
+    pub description: String,
+    pub field_type: FieldType,
+    pub unit: String,
+}
+
+impl MetadataField {
+    pub fn new(name: &str, description: &str, field_type: FieldType, unit: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            description: description.to_string(),
+            field_type,
+            unit: unit.to_string(),
+        }
+    }
+
+    /// The `seed` key every rhythm reports.
+    pub fn seed() -> Self {
+        Self::new("seed", "Seed of this rhythm's random stream", FieldType::Integer, "")
+    }
+
+    /// The `integration_scheme` key reported by rhythms with an integrator.
+    pub fn integration_scheme() -> Self {
+        Self::new("integration_scheme", "Active integration scheme", FieldType::String, "")
+    }
+}
+
+/// Layout of the frames a rhythm produces.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputSchema {
+    pub channels: Vec<ChannelSpec>,
+    pub metadata: Vec<MetadataField>,
+}
+
+/// Everything a generic client needs to work with a rhythm.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RhythmDescription {
+    pub rhythm_type: String,
+    pub output: OutputSchema,
+    pub parameters: Vec<ParameterSpec>,
+    pub integration_scheme: Option<IntegrationScheme>,
+}
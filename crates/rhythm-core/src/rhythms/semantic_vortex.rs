@@ -0,0 +1,257 @@
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use super::{ChannelSpec, FieldType, MetadataField, OutputSchema, ParameterSpec, Rhythm, RhythmParams};
+use crate::integrator::{IntegrationScheme, Integrator};
+use crate::RhythmData;
+use crate::rng::{random_seed, rng_from_seed, RhythmRng};
+use rand::Rng;
+use nalgebra::{Vector3, Point3};
+use std::any::Any;
+
+/// Out-of-plane velocity diffusion per sqrt(second).
+const Z_DIFFUSION: f64 = 0.013;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SemanticVortexParams {
+    /// Pull of attractors and push of repellers.
+    pub vortex_strength: f64,
+    /// Velocity damping rate, in 1/s.
+    pub damping: f64,
+}
+
+impl Default for SemanticVortexParams {
+    fn default() -> Self {
+        Self {
+            vortex_strength: 0.5,
+            damping: 1.2,
+        }
+    }
+}
+
+impl RhythmParams for SemanticVortexParams {
+    fn schema() -> Vec<ParameterSpec> {
+        let defaults = Self::default();
+        vec![
+            ParameterSpec::new("vortex_strength", "Pull of attractors and push of repellers", defaults.vortex_strength, 0.0, 5.0, ""),
+            ParameterSpec::new("damping", "Velocity damping rate", defaults.damping, 0.0, 10.0, "1/s"),
+        ]
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SemanticVortex {
+    attractors: Vec<Point3<f64>>,
+    repellers: Vec<Point3<f64>>,
+    current_position: Point3<f64>,
+    velocity: Vector3<f64>,
+    params: SemanticVortexParams,
+    integrator: Integrator,
+    seed: u64,
+    rng: RhythmRng,
+}
+
+impl Default for SemanticVortex {
+    fn default() -> Self {
+        Self::from_seed(random_seed())
+    }
+}
+
+impl SemanticVortex {
+    /// Create the rhythm with all randomness drawn from `seed`.
+    pub fn from_seed(seed: u64) -> Self {
+        let mut rng = rng_from_seed(seed);
+        
+        // Initialize random attractors and repellers
+        let attractors = (0..3).map(|_| {
+            Point3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+        }).collect();
+        
+        let repellers = (0..2).map(|_| {
+            Point3::new(
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+                rng.gen_range(-1.0..1.0),
+            )
+        }).collect();
+        
+        Self {
+            attractors,
+            repellers,
+            current_position: Point3::origin(),
+            velocity: Vector3::zeros(),
+            params: SemanticVortexParams::default(),
+            integrator: Integrator::default(),
+            seed,
+            rng,
+        }
+    }
+
+    /// Deterministic acceleration of a point at `position` moving with `velocity`.
+    fn acceleration(&self, position: &Point3<f64>, velocity: &Vector3<f64>) -> Vector3<f64> {
+        // Calculate forces from attractors and repellers
+        let mut force = Vector3::zeros();
+        
+        for attractor in &self.attractors {
+            let direction = attractor - position;
+            let distance = direction.norm();
+            if distance > 0.01 {
+                force += direction.normalize() * (self.params.vortex_strength / (distance + 0.1));
+            }
+        }
+        
+        for repeller in &self.repellers {
+            let direction = position - repeller;
+            let distance = direction.norm();
+            if distance > 0.01 {
+                force += direction.normalize() * (self.params.vortex_strength * 0.5 / (distance + 0.1));
+            }
+        }
+        
+        // Add vortex rotation
+        force += Vector3::new(-position.y * 0.5, position.x * 0.5, 0.0);
+
+        force - velocity * self.params.damping
+    }
+
+    fn integrate_step(&mut self, h: f64) {
+        let mut position = [self.current_position.x, self.current_position.y, self.current_position.z];
+        let mut velocity = [self.velocity.x, self.velocity.y, self.velocity.z];
+
+        self.integrator.scheme.step_second_order(&mut position, &mut velocity, h, |x, v, i| {
+            self.acceleration(&Point3::new(x[0], x[1], x[2]), &Vector3::new(v[0], v[1], v[2]))[i]
+        });
+
+        self.current_position = Point3::from(position);
+        self.velocity = Vector3::from(velocity);
+
+        // Random drift out of the vortex plane
+        self.velocity.z += self.rng.gen_range(-1.0..1.0) * Z_DIFFUSION * h.sqrt();
+    }
+}
+
+impl Rhythm for SemanticVortex {
+    fn name(&self) -> &'static str {
+        "semantic_vortex"
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn reseed(&mut self, seed: u64) {
+        let params = self.params.clone();
+        let scheme = self.integrator.scheme;
+        *self = Self::from_seed(seed);
+        self.params = params;
+        self.integrator.scheme = scheme;
+    }
+
+    fn update(&mut self, delta_time: f64) {
+        for _ in 0..self.integrator.substeps(delta_time) {
+            self.integrate_step(self.integrator.step());
+        }
+
+        let rng = &mut self.rng;
+
+        // Occasionally move attractors/repellers
+        if rng.gen::<f64>() < 0.01 * delta_time {
+            let index = rng.gen_range(0..self.attractors.len());
+            if let Some(attractor) = self.attractors.get_mut(index) {
+                *attractor += Vector3::new(
+                    rng.gen_range(-0.1..0.1),
+                    rng.gen_range(-0.1..0.1),
+                    rng.gen_range(-0.1..0.1),
+                );
+            }
+        }
+    }
+    
+    fn integration_scheme(&self) -> Option<IntegrationScheme> {
+        Some(self.integrator.scheme)
+    }
+
+    fn set_integration_scheme(&mut self, scheme: IntegrationScheme) -> Result<(), String> {
+        self.integrator.scheme = scheme;
+        Ok(())
+    }
+
+    fn output_values(&self) -> Vec<f64> {
+        vec![
+            self.current_position.x,
+            self.current_position.y,
+            self.current_position.z,
+            self.velocity.norm(),
+        ]
+    }
+
+    fn snapshot(&self, timestamp: f64) -> RhythmData {
+        let position_magnitude = self.current_position.coords.norm();
+        
+        RhythmData {
+            rhythm_type: self.name().to_string(),
+            timestamp,
+            values: self.output_values(),
+            metadata: json!({
+                "velocity": [self.velocity.x, self.velocity.y, self.velocity.z],
+                "num_attractors": self.attractors.len(),
+                "num_repellers": self.repellers.len(),
+                "position_magnitude": position_magnitude,
+                "seed": self.seed,
+                "integration_scheme": self.integrator.scheme,
+            }),
+        }
+    }
+
+    fn save_state(&self) -> Result<serde_json::Value, String> {
+        serde_json::to_value(self).map_err(|e| e.to_string())
+    }
+
+    fn load_state(&mut self, state: serde_json::Value) -> Result<(), String> {
+        let state: Self = serde_json::from_value(state).map_err(|e| e.to_string())?;
+        state.params.check()?;
+        state.integrator.validate()?;
+        *self = state;
+        Ok(())
+    }
+
+    fn output_schema(&self) -> OutputSchema {
+        OutputSchema {
+            channels: vec![
+                ChannelSpec::new("x", "Position in meaning space", "", -2.0, 2.0),
+                ChannelSpec::new("y", "Position in meaning space", "", -2.0, 2.0),
+                ChannelSpec::new("z", "Position in meaning space", "", -2.0, 2.0),
+                ChannelSpec::new("flow_magnitude", "Speed through meaning space", "1/s", 0.0, 2.0),
+            ],
+            metadata: vec![
+                MetadataField::new("velocity", "Velocity vector", FieldType::NumberArray, "1/s"),
+                MetadataField::new("num_attractors", "Number of attractors", FieldType::Integer, ""),
+                MetadataField::new("num_repellers", "Number of repellers", FieldType::Integer, ""),
+                MetadataField::new("position_magnitude", "Distance from the origin", FieldType::Number, ""),
+                MetadataField::seed(),
+                MetadataField::integration_scheme(),
+            ],
+        }
+    }
+
+    fn parameter_schema(&self) -> Vec<ParameterSpec> {
+        SemanticVortexParams::schema()
+    }
+
+    fn params(&self) -> serde_json::Value {
+        self.params.to_json()
+    }
+
+    fn set_params(&mut self, update: serde_json::Value) -> Result<serde_json::Value, String> {
+        self.params = self.params.merged(update)?;
+        Ok(self.params())
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
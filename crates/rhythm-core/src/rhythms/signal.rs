@@ -0,0 +1,146 @@
+use std::f64::consts::TAU;
+use std::fs;
+use std::path::Path;
+
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::rng::standard_normal;
+
+/// Where a rhythm's input signal comes from.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SignalSource {
+    /// No input.
+    None,
+    /// Output channel `channel` of the rhythm registered as `id`, as it was
+    /// at the start of the tick.
+    Rhythm { id: String, channel: usize },
+    /// Recorded samples played back at `sample_rate`, looping at the end.
+    Series { samples: Vec<f64>, sample_rate: f64 },
+    /// `amplitude · sin(2π f t)` plus Gaussian noise.
+    Sine { frequency: f64, amplitude: f64, noise: f64 },
+    /// Brownian motion with the given volatility, in 1/√s, plus Gaussian
+    /// observation noise.
+    RandomWalk { volatility: f64, noise: f64 },
+}
+
+impl Default for SignalSource {
+    fn default() -> Self {
+        SignalSource::None
+    }
+}
+
+impl SignalSource {
+    /// Most samples a series may hold.
+    pub const MAX_SAMPLES: usize = 1_000_000;
+
+    pub fn validate(&self) -> Result<(), String> {
+        match self {
+            SignalSource::Series { samples, sample_rate } => {
+                if samples.is_empty() || samples.len() > Self::MAX_SAMPLES {
+                    return Err(format!("A series must hold 1 to {} samples, got {}", Self::MAX_SAMPLES, samples.len()));
+                }
+                if samples.iter().any(|x| !x.is_finite()) {
+                    return Err("Series samples must be finite".to_string());
+                }
+                check_positive("sample_rate", *sample_rate)
+            }
+            SignalSource::Sine { frequency, amplitude, noise } => {
+                check_positive("frequency", *frequency)?;
+                check_non_negative("amplitude", *amplitude)?;
+                check_non_negative("noise", *noise)
+            }
+            SignalSource::RandomWalk { volatility, noise } => {
+                check_non_negative("volatility", *volatility)?;
+                check_non_negative("noise", *noise)
+            }
+            SignalSource::None | SignalSource::Rhythm { .. } => Ok(()),
+        }
+    }
+
+    /// Read a series from a file holding either a JSON array of numbers or
+    /// numbers separated by whitespace or commas.
+    pub fn load_series(path: impl AsRef<Path>, sample_rate: f64) -> Result<Self, String> {
+        let text = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let samples = match serde_json::from_str::<Vec<f64>>(&text) {
+            Ok(samples) => samples,
+            Err(_) => text
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|token| !token.is_empty())
+                .map(|token| token.parse::<f64>().map_err(|_| format!("Not a number: {}", token)))
+                .collect::<Result<_, _>>()?,
+        };
+        let source = SignalSource::Series { samples, sample_rate };
+        source.validate()?;
+        Ok(source)
+    }
+}
+
+fn check_positive(name: &str, value: f64) -> Result<(), String> {
+    if value > 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(format!("{} must be positive, got {}", name, value))
+    }
+}
+
+fn check_non_negative(name: &str, value: f64) -> Result<(), String> {
+    if value >= 0.0 && value.is_finite() {
+        Ok(())
+    } else {
+        Err(format!("{} must not be negative, got {}", name, value))
+    }
+}
+
+/// A signal source together with the state needed to sample it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SignalInput {
+    source: SignalSource,
+    /// Position of the random walk.
+    walk: f64,
+    /// Latest value delivered from a rhythm source, not yet consumed.
+    received: Option<f64>,
+}
+
+impl SignalInput {
+    pub fn new(source: SignalSource) -> Result<Self, String> {
+        source.validate()?;
+        Ok(Self {
+            source,
+            walk: 0.0,
+            received: None,
+        })
+    }
+
+    pub fn source(&self) -> &SignalSource {
+        &self.source
+    }
+
+    /// Accept the current value of a rhythm source.
+    pub fn receive(&mut self, value: f64) {
+        self.received = Some(value);
+    }
+
+    /// Value of the signal at `time`, after `delta_time` has passed since
+    /// the last sample; `None` if there is no input or nothing was received.
+    pub fn sample(&mut self, time: f64, delta_time: f64, rng: &mut impl Rng) -> Option<f64> {
+        match self.source {
+            SignalSource::None => None,
+            SignalSource::Rhythm { .. } => self.received.take(),
+            SignalSource::Series { ref samples, sample_rate } => {
+                let position = (time * sample_rate).rem_euclid(samples.len() as f64);
+                let index = position.floor() as usize % samples.len();
+                let next = samples[(index + 1) % samples.len()];
+                Some(samples[index] + (next - samples[index]) * position.fract())
+            }
+            SignalSource::Sine { frequency, amplitude, noise } => {
+                Some(amplitude * (TAU * frequency * time).sin() + noise * standard_normal(rng))
+            }
+            SignalSource::RandomWalk { volatility, noise } => {
+                self.walk += volatility * delta_time.sqrt() * standard_normal(rng);
+                Some(self.walk + noise * standard_normal(rng))
+            }
+        }
+    }
+}
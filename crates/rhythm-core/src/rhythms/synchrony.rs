@@ -0,0 +1,122 @@
+use serde::{Deserialize, Serialize};
+
+/// Complex Kuramoto order parameter `r·e^{iψ}` of a population of phases.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrderParameter {
+    /// Coherence `r` in [0, 1]: 0 for spread-out phases, 1 for identical ones.
+    pub magnitude: f64,
+    /// Mean phase `ψ`, in rad.
+    pub phase: f64,
+}
+
+impl OrderParameter {
+    pub fn of(phases: &[f64]) -> Self {
+        if phases.is_empty() {
+            return Self { magnitude: 0.0, phase: 0.0 };
+        }
+        let n = phases.len() as f64;
+        let re = phases.iter().map(|phase| phase.cos()).sum::<f64>() / n;
+        let im = phases.iter().map(|phase| phase.sin()).sum::<f64>() / n;
+        Self {
+            magnitude: re.hypot(im),
+            phase: im.atan2(re),
+        }
+    }
+}
+
+/// Coarse synchronization regime, judged from the time-averaged order parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncState {
+    Incoherent,
+    Partial,
+    Synchronized,
+}
+
+impl SyncState {
+    pub fn classify(mean_order: f64, incoherence_threshold: f64, sync_threshold: f64) -> Self {
+        if mean_order >= sync_threshold {
+            SyncState::Synchronized
+        } else if mean_order < incoherence_threshold {
+            SyncState::Incoherent
+        } else {
+            SyncState::Partial
+        }
+    }
+}
+
+/// Exponentially weighted averages of the order parameter and of pairwise
+/// phase differences, from which phase-locking values are read.
+///
+/// The averages are bias-corrected, so they are meaningful from the first
+/// sample rather than ramping up from zero. Observing a population of a
+/// different size starts the averages over.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncTracker {
+    count: usize,
+    /// Uncorrected average of `e^{i(θ_j − θ_k)}` for each pair `j < k`, as (re, im).
+    pairs: Vec<(f64, f64)>,
+    /// Uncorrected average of the order parameter magnitude.
+    order: f64,
+    /// Total weight of the samples folded in so far, used to correct the averages.
+    weight: f64,
+}
+
+impl SyncTracker {
+    /// Fold in the phases after `dt` seconds, averaging over roughly `window` seconds.
+    pub fn observe(&mut self, phases: &[f64], dt: f64, window: f64) {
+        let count = phases.len();
+        if count != self.count {
+            *self = Self {
+                count,
+                pairs: vec![(0.0, 0.0); count * count.saturating_sub(1) / 2],
+                order: 0.0,
+                weight: 0.0,
+            };
+        }
+
+        let alpha = 1.0 - (-dt / window).exp();
+        let unit: Vec<(f64, f64)> = phases.iter().map(|phase| (phase.cos(), phase.sin())).collect();
+
+        for ((j, k), sum) in pairs(count).zip(&mut self.pairs) {
+            // e^{iθ_j} · e^{-iθ_k}
+            let re = unit[j].0 * unit[k].0 + unit[j].1 * unit[k].1;
+            let im = unit[j].1 * unit[k].0 - unit[j].0 * unit[k].1;
+            sum.0 += alpha * (re - sum.0);
+            sum.1 += alpha * (im - sum.1);
+        }
+
+        self.order += alpha * (OrderParameter::of(phases).magnitude - self.order);
+        self.weight += alpha * (1.0 - self.weight);
+    }
+
+    /// Time-averaged order parameter magnitude.
+    pub fn mean_order(&self) -> f64 {
+        if self.weight > 0.0 {
+            self.order / self.weight
+        } else {
+            0.0
+        }
+    }
+
+    /// Symmetric matrix of pairwise phase-locking values in [0, 1], with ones
+    /// on the diagonal.
+    pub fn phase_locking(&self) -> Vec<Vec<f64>> {
+        let mut plv = vec![vec![1.0; self.count]; self.count];
+        if self.weight <= 0.0 {
+            return plv;
+        }
+
+        for ((j, k), (re, im)) in pairs(self.count).zip(&self.pairs) {
+            let value = (re.hypot(*im) / self.weight).min(1.0);
+            plv[j][k] = value;
+            plv[k][j] = value;
+        }
+        plv
+    }
+}
+
+/// Index pairs `(j, k)` with `j < k`, in the order the tracker stores them.
+fn pairs(count: usize) -> impl Iterator<Item = (usize, usize)> {
+    (0..count).flat_map(move |j| ((j + 1)..count).map(move |k| (j, k)))
+}
@@ -0,0 +1,199 @@
+use std::collections::VecDeque;
+
+use serde::{Deserialize, Serialize};
+
+/// Ring buffer of the most recent samples with their mean and variance kept
+/// up to date in O(1) per sample by Welford's method, run forwards for new
+/// samples and backwards for evicted ones.
+///
+/// Rounding error from the backward steps is cleared by an exact
+/// recomputation once per buffer length of evictions.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(from = "WindowRepr")]
+pub struct SlidingWindow {
+    samples: VecDeque<f64>,
+    mean: f64,
+    /// Sum of squared deviations from the mean.
+    m2: f64,
+    /// Evictions since the last exact recomputation.
+    evictions: usize,
+}
+
+/// Also accepts the plain sample arrays written before running statistics
+/// were kept.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum WindowRepr {
+    Full {
+        samples: VecDeque<f64>,
+        mean: f64,
+        m2: f64,
+        evictions: usize,
+    },
+    Samples(VecDeque<f64>),
+}
+
+impl From<WindowRepr> for SlidingWindow {
+    fn from(repr: WindowRepr) -> Self {
+        match repr {
+            WindowRepr::Full { samples, mean, m2, evictions } => Self { samples, mean, m2, evictions },
+            WindowRepr::Samples(samples) => {
+                let mut window = Self {
+                    samples,
+                    ..Self::default()
+                };
+                window.recompute();
+                window
+            }
+        }
+    }
+}
+
+impl SlidingWindow {
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn sum(&self) -> f64 {
+        self.mean * self.samples.len() as f64
+    }
+
+    /// Population variance of the samples.
+    pub fn variance(&self) -> f64 {
+        if self.samples.len() > 1 {
+            self.m2 / self.samples.len() as f64
+        } else {
+            0.0
+        }
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Append `value`, evicting the oldest samples beyond `capacity`.
+    pub fn push(&mut self, value: f64, capacity: usize) {
+        self.samples.push_back(value);
+        let n = self.samples.len() as f64;
+        let delta = value - self.mean;
+        self.mean += delta / n;
+        self.m2 += delta * (value - self.mean);
+        self.truncate(capacity);
+    }
+
+    /// Evict the oldest samples beyond `capacity`.
+    pub fn truncate(&mut self, capacity: usize) {
+        while self.samples.len() > capacity {
+            let value = match self.samples.pop_front() {
+                Some(value) => value,
+                None => break,
+            };
+            if self.samples.is_empty() {
+                self.clear();
+                break;
+            }
+            let n = self.samples.len() as f64;
+            let delta = value - self.mean;
+            self.mean -= delta / n;
+            self.m2 = (self.m2 - delta * (value - self.mean)).max(0.0);
+            self.evictions += 1;
+        }
+        if self.evictions >= self.samples.len().max(1) {
+            self.recompute();
+        }
+    }
+
+    /// Exact two-pass mean and variance.
+    fn recompute(&mut self) {
+        let n = self.samples.len().max(1) as f64;
+        self.mean = self.samples.iter().sum::<f64>() / n;
+        self.m2 = self.samples.iter().map(|x| (x - self.mean).powi(2)).sum();
+        self.evictions = 0;
+    }
+}
+
+/// Exponentially weighted mean and variance with a time constant, for
+/// averages over windows too long to buffer. Both are corrected for the
+/// bias of starting from nothing, so the first sample is the mean.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Ewma {
+    mean: f64,
+    variance: f64,
+    /// Total weight of the samples seen, approaching 1.
+    weight: f64,
+}
+
+impl Ewma {
+    pub fn mean(&self) -> f64 {
+        self.mean
+    }
+
+    pub fn variance(&self) -> f64 {
+        self.variance
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Add a sample representing `delta_time` seconds, with older samples
+    /// fading by `e` every `time_constant` seconds.
+    pub fn observe(&mut self, value: f64, delta_time: f64, time_constant: f64) {
+        let alpha = 1.0 - (-delta_time / time_constant).exp();
+        self.weight += alpha * (1.0 - self.weight);
+        if self.weight <= 0.0 {
+            return;
+        }
+        let step = alpha / self.weight;
+        let delta = value - self.mean;
+        self.mean += step * delta;
+        self.variance = (1.0 - step) * (self.variance + step * delta * delta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two-pass mean and population variance of `samples`.
+    fn two_pass(samples: &[f64]) -> (f64, f64) {
+        let n = samples.len() as f64;
+        let mean = samples.iter().sum::<f64>() / n;
+        let variance = samples.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n;
+        (mean, variance)
+    }
+
+    #[test]
+    fn sliding_window_matches_two_pass_after_evictions() {
+        let capacity = 37;
+        let values: Vec<f64> = (0..2000).map(|i| 1e6 + (i as f64 * 0.7).sin() * 3.0 + (i % 11) as f64).collect();
+        let mut window = SlidingWindow::default();
+        for (i, &value) in values.iter().enumerate() {
+            window.push(value, capacity);
+            let (mean, variance) = two_pass(&values[(i + 1).saturating_sub(capacity)..=i]);
+            assert!((window.mean() - mean).abs() < 1e-9 * mean.abs(), "mean at {}", i);
+            assert!((window.variance() - variance).abs() < 1e-6 * variance.max(1.0), "variance at {}", i);
+        }
+
+        window.truncate(5);
+        let (mean, variance) = two_pass(&values[values.len() - 5..]);
+        assert_eq!(window.len(), 5);
+        assert!((window.mean() - mean).abs() < 1e-9 * mean.abs());
+        assert!((window.variance() - variance).abs() < 1e-6 * variance.max(1.0));
+    }
+
+    #[test]
+    fn sliding_window_reads_plain_samples() {
+        let window: SlidingWindow = serde_json::from_str("[1.0, 2.0, 3.0, 4.0]").unwrap();
+        assert_eq!(window.mean(), 2.5);
+        assert_eq!(window.variance(), 1.25);
+    }
+}
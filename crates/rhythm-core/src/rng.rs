@@ -0,0 +1,47 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Random number generator used by every rhythm.
+///
+/// ChaCha is used rather than `StdRng` because its output is fixed across
+/// platforms and `rand` releases, which keeps recorded seeds replayable.
+pub type RhythmRng = ChaCha8Rng;
+
+/// Seeds are kept within the range a JavaScript number can represent exactly,
+/// so values reported to the frontend can be sent back unchanged.
+const SEED_MASK: u64 = (1 << 53) - 1;
+
+pub fn rng_from_seed(seed: u64) -> RhythmRng {
+    RhythmRng::seed_from_u64(seed)
+}
+
+/// Draw a fresh seed from system entropy.
+pub fn random_seed() -> u64 {
+    rand::random::<u64>() & SEED_MASK
+}
+
+/// Derive the seed of one rhythm's stream from a master seed.
+///
+/// The rhythm id is hashed with FNV-1a and mixed into the master seed with a
+/// SplitMix64 finalizer, so each rhythm gets an independent stream that does
+/// not depend on registration order.
+pub fn derive_seed(master_seed: u64, rhythm_id: &str) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in rhythm_id.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+
+    let mut z = master_seed ^ hash;
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    (z ^ (z >> 31)) & SEED_MASK
+}
+
+/// Box-Muller draw from the standard normal distribution.
+pub fn standard_normal(rng: &mut impl Rng) -> f64 {
+    let u: f64 = 1.0 - rng.gen::<f64>();
+    let v: f64 = rng.gen();
+    (-2.0 * u.ln()).sqrt() * (std::f64::consts::TAU * v).cos()
+}
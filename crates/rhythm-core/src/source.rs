@@ -0,0 +1,27 @@
+use crate::{LabeledRhythmData, OutputSchema, RhythmData, RhythmRegistry};
+
+/// Anything that can produce rhythm frames: the live simulation or a replay.
+pub trait FrameSource {
+    fn frame(&self, rhythm_type: &str, timestamp: f64) -> Result<RhythmData, String>;
+
+    fn output_schema(&self, rhythm_type: &str) -> Result<OutputSchema, String>;
+
+    fn labeled_frame(&self, rhythm_type: &str, timestamp: f64) -> Result<LabeledRhythmData, String> {
+        let schema = self.output_schema(rhythm_type)?;
+        Ok(self.frame(rhythm_type, timestamp)?.labeled(&schema))
+    }
+}
+
+impl FrameSource for RhythmRegistry {
+    fn frame(&self, rhythm_type: &str, timestamp: f64) -> Result<RhythmData, String> {
+        let rhythm = self.get(rhythm_type)?;
+        let rhythm = rhythm.lock().unwrap();
+        Ok(rhythm.snapshot(timestamp))
+    }
+
+    fn output_schema(&self, rhythm_type: &str) -> Result<OutputSchema, String> {
+        let rhythm = self.get(rhythm_type)?;
+        let rhythm = rhythm.lock().unwrap();
+        Ok(rhythm.output_schema())
+    }
+}
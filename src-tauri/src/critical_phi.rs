@@ -0,0 +1,63 @@
+use rhythm_core::{Avalanche, AvalancheStatistics, CriticalPhi, CriticalityEngine, MatrixConfig};
+use tauri::State;
+
+use crate::AppState;
+
+const CRITICAL_PHI: &str = "critical_phi";
+
+/// The most recent `limit` avalanches of the critical-phi rhythm, oldest first.
+#[tauri::command]
+pub fn get_avalanches(limit: Option<usize>, state: State<AppState>) -> Result<Vec<Avalanche>, String> {
+    state.registry.with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| {
+        Ok(rhythm.avalanches().recent(limit.unwrap_or(usize::MAX)))
+    })
+}
+
+/// Power-law fits, histograms and branching ratio of the logged avalanches.
+#[tauri::command]
+pub fn get_avalanche_statistics(state: State<AppState>) -> Result<AvalancheStatistics, String> {
+    state
+        .registry
+        .with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| Ok(rhythm.avalanche_statistics()))
+}
+
+#[tauri::command]
+pub fn clear_avalanches(state: State<AppState>) -> Result<(), String> {
+    state.registry.with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| {
+        rhythm.clear_avalanches();
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn get_criticality_engine(state: State<AppState>) -> Result<CriticalityEngine, String> {
+    state
+        .registry
+        .with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| Ok(rhythm.engine()))
+}
+
+/// Switch the mechanism producing avalanches; the avalanche log starts over.
+#[tauri::command]
+pub fn set_criticality_engine(engine: CriticalityEngine, state: State<AppState>) -> Result<(), String> {
+    state.registry.with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| {
+        rhythm.set_engine(engine);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn get_matrix_config(state: State<AppState>) -> Result<MatrixConfig, String> {
+    state
+        .registry
+        .with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| Ok(rhythm.matrix_config().clone()))
+}
+
+/// Draw a new integration matrix and return its weights; the avalanche log
+/// starts over.
+#[tauri::command]
+pub fn configure_matrix(config: MatrixConfig, state: State<AppState>) -> Result<Vec<Vec<f64>>, String> {
+    state.registry.with_rhythm(CRITICAL_PHI, |rhythm: &mut CriticalPhi| {
+        rhythm.configure_matrix(config)?;
+        Ok(rhythm.integration_matrix().to_vec())
+    })
+}
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+use rhythm_core::FrameSource;
+use serde::Serialize;
+use tauri::Window;
+
+/// What a subscriber needs to start listening for frames.
+#[derive(Debug, Clone, Serialize)]
+pub struct SubscriptionInfo {
+    pub id: u64,
+    pub event: String,
+    pub rhythm_type: String,
+}
+
+struct Subscription {
+    rhythm_type: String,
+    window: Window,
+    /// Requested frames per second, or `None` for every tick.
+    rate: Option<f64>,
+    /// Emit frames with values keyed by channel name.
+    labeled: bool,
+    last_tick: Option<u64>,
+}
+
+impl Subscription {
+    /// Number of ticks between frames at the given clock rate; saturates at
+    /// `u64::MAX` for tiny rates.
+    fn decimation(&self, tick_rate: f64) -> u64 {
+        match self.rate {
+            Some(rate) => (tick_rate / rate).round().max(1.0) as u64,
+            None => 1,
+        }
+    }
+
+    fn is_due(&self, tick_count: u64, tick_rate: f64) -> bool {
+        match self.last_tick {
+            Some(last_tick) => tick_count.saturating_sub(last_tick) >= self.decimation(tick_rate),
+            None => true,
+        }
+    }
+}
+
+/// Windows observing rhythm frames, each at its own rate.
+///
+/// Subscribers only observe; the simulation clock is what advances the
+/// rhythms, so any number of panels can watch the same state.
+#[derive(Default)]
+pub struct Subscriptions {
+    next_id: Mutex<u64>,
+    subscriptions: Mutex<BTreeMap<u64, Subscription>>,
+}
+
+impl Subscriptions {
+    pub fn subscribe(
+        &self,
+        rhythm_type: String,
+        window: Window,
+        rate: Option<f64>,
+        labeled: bool,
+    ) -> Result<SubscriptionInfo, String> {
+        if let Some(rate) = rate {
+            if !(rate > 0.0 && rate.is_finite()) {
+                return Err(format!("Frame rate must be positive, got {}", rate));
+            }
+        }
+
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            *next_id
+        };
+
+        let info = SubscriptionInfo {
+            id,
+            event: event_name(id),
+            rhythm_type: rhythm_type.clone(),
+        };
+
+        self.subscriptions.lock().unwrap().insert(
+            id,
+            Subscription {
+                rhythm_type,
+                window,
+                rate,
+                labeled,
+                last_tick: None,
+            },
+        );
+
+        Ok(info)
+    }
+
+    pub fn unsubscribe(&self, id: u64) -> bool {
+        self.subscriptions.lock().unwrap().remove(&id).is_some()
+    }
+
+    /// Drop every subscription held by a window, e.g. when it closes.
+    pub fn remove_window(&self, label: &str) {
+        self.subscriptions
+            .lock()
+            .unwrap()
+            .retain(|_, subscription| subscription.window.label() != label);
+    }
+
+    /// Emit a frame from `source` to every subscriber that is due at `tick_count`.
+    ///
+    /// Subscriptions whose window can no longer be reached are dropped. A
+    /// rhythm missing from the source, e.g. one absent from a replay, is
+    /// skipped without ending the subscription.
+    pub fn publish(&self, source: &dyn FrameSource, tick_count: u64, tick_rate: f64) {
+        let timestamp = crate::timestamp();
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+
+        subscriptions.retain(|id, subscription| {
+            if !subscription.is_due(tick_count, tick_rate) {
+                return true;
+            }
+            subscription.last_tick = Some(tick_count);
+
+            let event = event_name(*id);
+            let sent = if subscription.labeled {
+                match source.labeled_frame(&subscription.rhythm_type, timestamp) {
+                    Ok(frame) => subscription.window.emit(&event, frame),
+                    Err(_) => return true,
+                }
+            } else {
+                match source.frame(&subscription.rhythm_type, timestamp) {
+                    Ok(frame) => subscription.window.emit(&event, frame),
+                    Err(_) => return true,
+                }
+            };
+            sent.is_ok()
+        });
+    }
+
+    /// Make every subscriber due on the next publish, e.g. after switching
+    /// between live and replayed frames whose tick counters differ.
+    pub fn reset(&self) {
+        for subscription in self.subscriptions.lock().unwrap().values_mut() {
+            subscription.last_tick = None;
+        }
+    }
+}
+
+fn event_name(id: u64) -> String {
+    format!("rhythm-frame-{}", id)
+}
@@ -3,99 +3,332 @@
     windows_subsystem = "windows"
 )]
 
-use serde::{Deserialize, Serialize};
 use std::sync::{Arc, Mutex};
-use tauri::State;
 
-mod rhythms;
+use rhythm_core::rng::random_seed;
+use rhythm_core::{
+    FrameSource, IntegrationScheme, LabeledRhythmData, ParameterSpec, RhythmData, RhythmDescription,
+    RhythmRegistry, SessionRecorder, SessionReplay, SimulationClock, SimulationSnapshot,
+};
+use tauri::{Manager, State, Window, WindowEvent};
 
-use rhythms::*;
+mod critical_phi;
+mod events;
+mod oscillators;
+mod prediction;
+mod session;
+mod simulation;
 
-#[derive(Default)]
+use events::{SubscriptionInfo, Subscriptions};
+
+#[derive(Default, Clone)]
 struct AppState {
-    multi_temporal: Arc<Mutex<MultiTemporalRhythm>>,
-    critical_phi: Arc<Mutex<CriticalPhi>>,
-    prediction_tension: Arc<Mutex<PredictionTension>>,
-    semantic_vortex: Arc<Mutex<SemanticVortex>>,
-    attention_wandering: Arc<Mutex<AttentionWandering>>,
+    registry: Arc<RhythmRegistry>,
+    clock: Arc<Mutex<SimulationClock>>,
+    subscriptions: Arc<Subscriptions>,
+    recorder: Arc<Mutex<Option<SessionRecorder>>>,
+    /// Why the last recording ended on its own, reported by `stop_recording`.
+    recording_error: Arc<Mutex<Option<String>>>,
+    replay: Arc<Mutex<Option<SessionReplay>>>,
 }
 
-#[derive(Serialize, Deserialize)]
-struct RhythmData {
-    rhythm_type: String,
-    timestamp: f64,
-    values: Vec<f64>,
-    metadata: serde_json::Value,
+impl AppState {
+    /// Run `f` against the loaded replay, or the live simulation if there is none.
+    fn with_source<T>(&self, f: impl FnOnce(&dyn FrameSource) -> T) -> T {
+        let replay = self.replay.lock().unwrap();
+        match replay.as_ref() {
+            Some(replay) => f(replay),
+            None => f(&*self.registry),
+        }
+    }
+
+    /// Record and publish the frames of a tick the live simulation just made.
+    /// Nothing is published while a replay is loaded, as subscribers are
+    /// watching the replay.
+    fn after_tick(&self, clock: &SimulationClock) {
+        {
+            let mut recorder = self.recorder.lock().unwrap();
+            if let Some(active) = recorder.as_mut() {
+                if let Err(e) = active.record(&self.registry, clock, timestamp()) {
+                    *self.recording_error.lock().unwrap() = Some(e);
+                    *recorder = None;
+                }
+            }
+        }
+        if self.replay.lock().unwrap().is_some() {
+            return;
+        }
+        self.subscriptions
+            .publish(&*self.registry, clock.tick_count, clock.tick_rate);
+    }
 }
 
-#[tauri::command]
-fn get_rhythm_data(rhythm_type: String, state: State<AppState>) -> Result<RhythmData, String> {
-    let timestamp = std::time::SystemTime::now()
+/// Wall-clock time in seconds since the Unix epoch, used to stamp frames.
+fn timestamp() -> f64 {
+    std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .unwrap()
-        .as_secs_f64();
+        .as_secs_f64()
+}
 
-    match rhythm_type.as_str() {
-        "multi_temporal" => {
-            let rhythm = state.multi_temporal.lock().unwrap();
-            Ok(rhythm.get_current_state(timestamp))
-        }
-        "critical_phi" => {
-            let rhythm = state.critical_phi.lock().unwrap();
-            Ok(rhythm.get_current_state(timestamp))
-        }
-        "prediction_tension" => {
-            let rhythm = state.prediction_tension.lock().unwrap();
-            Ok(rhythm.get_current_state(timestamp))
-        }
-        "semantic_vortex" => {
-            let rhythm = state.semantic_vortex.lock().unwrap();
-            Ok(rhythm.get_current_state(timestamp))
-        }
-        "attention_wandering" => {
-            let rhythm = state.attention_wandering.lock().unwrap();
-            Ok(rhythm.get_current_state(timestamp))
-        }
-        _ => Err("Unknown rhythm type".to_string()),
+#[tauri::command]
+fn get_rhythm_data(rhythm_type: String, state: State<AppState>) -> Result<RhythmData, String> {
+    state.with_source(|source| source.frame(&rhythm_type, timestamp()))
+}
+
+#[tauri::command]
+fn get_labeled_rhythm_data(rhythm_type: String, state: State<AppState>) -> Result<LabeledRhythmData, String> {
+    state.with_source(|source| source.labeled_frame(&rhythm_type, timestamp()))
+}
+
+/// Channel and metadata layout plus parameters of a rhythm.
+#[tauri::command]
+fn describe_rhythm(rhythm_type: String, state: State<AppState>) -> Result<RhythmDescription, String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let rhythm = rhythm.lock().unwrap();
+    Ok(rhythm.describe())
+}
+
+/// Start pushing frames of a rhythm to the calling window.
+///
+/// Frames arrive on the returned event name at most `rate` times per second,
+/// or on every simulation tick when no rate is given. With `labeled` set,
+/// frames carry values keyed by channel name as described by `describe_rhythm`.
+#[tauri::command]
+fn subscribe_rhythm(
+    rhythm_type: String,
+    rate: Option<f64>,
+    labeled: Option<bool>,
+    window: Window,
+    state: State<AppState>,
+) -> Result<SubscriptionInfo, String> {
+    state.registry.get(&rhythm_type)?;
+    state
+        .subscriptions
+        .subscribe(rhythm_type, window, rate, labeled.unwrap_or(false))
+}
+
+#[tauri::command]
+fn unsubscribe_rhythm(id: u64, state: State<AppState>) -> Result<(), String> {
+    if state.subscriptions.unsubscribe(id) {
+        Ok(())
+    } else {
+        Err(format!("Unknown subscription: {}", id))
     }
 }
 
 #[tauri::command]
-fn update_rhythm(rhythm_type: String, delta_time: f64, state: State<AppState>) -> Result<(), String> {
-    match rhythm_type.as_str() {
-        "multi_temporal" => {
-            let mut rhythm = state.multi_temporal.lock().unwrap();
-            rhythm.update(delta_time);
-            Ok(())
-        }
-        "critical_phi" => {
-            let mut rhythm = state.critical_phi.lock().unwrap();
-            rhythm.update(delta_time);
-            Ok(())
-        }
-        "prediction_tension" => {
-            let mut rhythm = state.prediction_tension.lock().unwrap();
-            rhythm.update(delta_time);
-            Ok(())
-        }
-        "semantic_vortex" => {
-            let mut rhythm = state.semantic_vortex.lock().unwrap();
-            rhythm.update(delta_time);
-            Ok(())
-        }
-        "attention_wandering" => {
-            let mut rhythm = state.attention_wandering.lock().unwrap();
-            rhythm.update(delta_time);
-            Ok(())
+fn get_clock_state(state: State<AppState>) -> SimulationClock {
+    state.clock.lock().unwrap().clone()
+}
+
+#[tauri::command]
+fn pause_simulation(state: State<AppState>) {
+    state.clock.lock().unwrap().paused = true;
+}
+
+#[tauri::command]
+fn resume_simulation(state: State<AppState>) {
+    state.clock.lock().unwrap().paused = false;
+}
+
+/// Advance the simulation by a number of ticks, at most
+/// `SimulationClock::MAX_STEPS`, typically while paused. Every tick is
+/// recorded and published as if the clock had run.
+#[tauri::command]
+fn step_simulation(steps: Option<u32>, state: State<AppState>) -> Result<SimulationClock, String> {
+    let mut clock = state.clock.lock().unwrap();
+    clock.step(&state.registry, steps.unwrap_or(1), |clock| state.after_tick(clock))?;
+    Ok(clock.clone())
+}
+
+#[tauri::command]
+fn set_time_scale(time_scale: f64, state: State<AppState>) -> Result<(), String> {
+    state.clock.lock().unwrap().set_time_scale(time_scale)
+}
+
+#[tauri::command]
+fn set_tick_rate(tick_rate: f64, state: State<AppState>) -> Result<(), String> {
+    state.clock.lock().unwrap().set_tick_rate(tick_rate)
+}
+
+#[tauri::command]
+fn list_rhythms(state: State<AppState>) -> Vec<String> {
+    state.registry.ids()
+}
+
+#[tauri::command]
+fn get_parameter_schema(rhythm_type: String, state: State<AppState>) -> Result<Vec<ParameterSpec>, String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let rhythm = rhythm.lock().unwrap();
+    Ok(rhythm.parameter_schema())
+}
+
+#[tauri::command]
+fn get_rhythm_params(rhythm_type: String, state: State<AppState>) -> Result<serde_json::Value, String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let rhythm = rhythm.lock().unwrap();
+    Ok(rhythm.params())
+}
+
+/// Update some or all parameters of a rhythm and return the full set.
+#[tauri::command]
+fn set_rhythm_params(
+    rhythm_type: String,
+    params: serde_json::Value,
+    state: State<AppState>,
+) -> Result<serde_json::Value, String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let mut rhythm = rhythm.lock().unwrap();
+    rhythm.set_params(params)
+}
+
+#[tauri::command]
+fn get_integration_scheme(rhythm_type: String, state: State<AppState>) -> Result<Option<IntegrationScheme>, String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let rhythm = rhythm.lock().unwrap();
+    Ok(rhythm.integration_scheme())
+}
+
+#[tauri::command]
+fn set_integration_scheme(
+    rhythm_type: String,
+    scheme: IntegrationScheme,
+    state: State<AppState>,
+) -> Result<(), String> {
+    let rhythm = state.registry.get(&rhythm_type)?;
+    let mut rhythm = rhythm.lock().unwrap();
+    rhythm.set_integration_scheme(scheme)
+}
+
+/// Reseed the simulation and return the seed that was applied.
+///
+/// With `rhythm_type` set, only that rhythm is reset and `seed` is used as its
+/// own stream seed (as reported in its metadata); otherwise `seed` becomes the
+/// master seed for every rhythm. A random seed is drawn when none is given.
+#[tauri::command]
+fn reseed(seed: Option<u64>, rhythm_type: Option<String>, state: State<AppState>) -> Result<u64, String> {
+    let seed = seed.unwrap_or_else(random_seed);
+
+    match rhythm_type {
+        Some(rhythm_type) => {
+            let rhythm = state.registry.get(&rhythm_type)?;
+            rhythm.lock().unwrap().reseed(seed);
         }
-        _ => Err("Unknown rhythm type".to_string()),
+        None => state.registry.reseed(seed),
     }
+
+    Ok(seed)
+}
+
+/// Write the complete simulation state to `path` as versioned JSON.
+#[tauri::command]
+fn save_snapshot(path: String, state: State<AppState>) -> Result<(), String> {
+    let snapshot = {
+        let clock = state.clock.lock().unwrap();
+        SimulationSnapshot::capture(&state.registry, &clock)?
+    };
+    snapshot.save(path)
+}
+
+/// Resume the simulation from a file written by `save_snapshot`.
+///
+/// Refused while recording, since the recording would mix the ticks from
+/// before and after the load.
+#[tauri::command]
+fn load_snapshot(path: String, state: State<AppState>) -> Result<SimulationClock, String> {
+    let snapshot = SimulationSnapshot::load(path)?;
+    let clock = {
+        let mut clock = state.clock.lock().unwrap();
+        if state.recorder.lock().unwrap().is_some() {
+            return Err("Stop the recording before loading a snapshot".to_string());
+        }
+        snapshot.restore(&state.registry, &mut clock)?;
+        clock.clone()
+    };
+    // The restored tick count may be behind the one subscribers last saw
+    state.subscriptions.reset();
+    Ok(clock)
+}
+
+#[tauri::command]
+fn get_master_seed(state: State<AppState>) -> u64 {
+    state.registry.master_seed()
 }
 
 fn main() {
+    let state = AppState::default();
+    simulation::spawn(state.clone());
+
     tauri::Builder::default()
-        .manage(AppState::default())
-        .invoke_handler(tauri::generate_handler![get_rhythm_data, update_rhythm])
+        .manage(state)
+        .on_window_event(|event| {
+            if let WindowEvent::Destroyed = event.event() {
+                let state = event.window().state::<AppState>();
+                state.subscriptions.remove_window(event.window().label());
+            }
+        })
+        .invoke_handler(tauri::generate_handler![
+            get_rhythm_data,
+            get_labeled_rhythm_data,
+            describe_rhythm,
+            subscribe_rhythm,
+            unsubscribe_rhythm,
+            get_clock_state,
+            pause_simulation,
+            resume_simulation,
+            step_simulation,
+            set_time_scale,
+            set_tick_rate,
+            list_rhythms,
+            get_parameter_schema,
+            get_rhythm_params,
+            set_rhythm_params,
+            get_integration_scheme,
+            set_integration_scheme,
+            reseed,
+            save_snapshot,
+            load_snapshot,
+            oscillators::get_oscillators,
+            oscillators::add_oscillator,
+            oscillators::remove_oscillator,
+            oscillators::update_oscillator,
+            oscillators::load_oscillator_bank,
+            oscillators::save_oscillator_bank,
+            oscillators::generate_oscillator_bank,
+            oscillators::get_oscillator_model,
+            oscillators::set_oscillator_model,
+            oscillators::get_coupling,
+            oscillators::set_coupling,
+            oscillators::generate_coupling,
+            critical_phi::get_avalanches,
+            critical_phi::get_avalanche_statistics,
+            critical_phi::clear_avalanches,
+            critical_phi::get_criticality_engine,
+            critical_phi::set_criticality_engine,
+            critical_phi::get_matrix_config,
+            critical_phi::configure_matrix,
+            prediction::get_prediction_input,
+            prediction::set_prediction_input,
+            prediction::load_prediction_series,
+            prediction::get_predictor,
+            prediction::set_predictor,
+            prediction::get_release_shape,
+            prediction::set_release_shape,
+            prediction::get_releases,
+            prediction::clear_releases,
+            session::start_recording,
+            session::stop_recording,
+            session::start_replay,
+            session::stop_replay,
+            session::get_replay_status,
+            session::seek_replay,
+            session::set_replay_speed,
+            session::set_replay_looping,
+            session::pause_replay,
+            session::resume_replay,
+            get_master_seed
+        ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+use rhythm_core::{
+    CouplingMatrix, MultiTemporalRhythm, Oscillator, OscillatorBank, OscillatorModel, OscillatorUpdate, Topology,
+};
+use tauri::State;
+
+use crate::AppState;
+
+const MULTI_TEMPORAL: &str = "multi_temporal";
+
+/// Oscillators of the multi-temporal rhythm in their current state.
+#[tauri::command]
+pub fn get_oscillators(state: State<AppState>) -> Result<Vec<Oscillator>, String> {
+    with_oscillators(&state, |_| Ok(()))
+}
+
+#[tauri::command]
+pub fn add_oscillator(oscillator: Oscillator, state: State<AppState>) -> Result<Vec<Oscillator>, String> {
+    with_oscillators(&state, |rhythm| rhythm.add_oscillator(oscillator))
+}
+
+#[tauri::command]
+pub fn remove_oscillator(index: usize, state: State<AppState>) -> Result<Vec<Oscillator>, String> {
+    with_oscillators(&state, |rhythm| rhythm.remove_oscillator(index).map(|_| ()))
+}
+
+/// Change some fields of one oscillator; the others keep their value.
+#[tauri::command]
+pub fn update_oscillator(
+    index: usize,
+    update: OscillatorUpdate,
+    state: State<AppState>,
+) -> Result<Vec<Oscillator>, String> {
+    with_oscillators(&state, |rhythm| rhythm.update_oscillator(index, &update).map(|_| ()))
+}
+
+/// Replace the oscillators with a bank read from a JSON file.
+#[tauri::command]
+pub fn load_oscillator_bank(path: String, state: State<AppState>) -> Result<Vec<Oscillator>, String> {
+    let bank = OscillatorBank::load(path)?;
+    with_oscillators(&state, |rhythm| rhythm.set_bank(bank))
+}
+
+/// Write the configured bank, with its starting phases, to a JSON file.
+#[tauri::command]
+pub fn save_oscillator_bank(path: String, state: State<AppState>) -> Result<(), String> {
+    let bank = state
+        .registry
+        .with_rhythm(MULTI_TEMPORAL, |rhythm: &mut MultiTemporalRhythm| Ok(rhythm.bank().clone()))?;
+    bank.save(path)
+}
+
+/// Replace the oscillators with `count` log-spaced time scales.
+#[tauri::command]
+pub fn generate_oscillator_bank(
+    count: usize,
+    highest_frequency: f64,
+    lowest_frequency: f64,
+    state: State<AppState>,
+) -> Result<Vec<Oscillator>, String> {
+    let bank = OscillatorBank::log_spaced(count, highest_frequency, lowest_frequency)?;
+    with_oscillators(&state, |rhythm| rhythm.set_bank(bank))
+}
+
+#[tauri::command]
+pub fn get_oscillator_model(state: State<AppState>) -> Result<OscillatorModel, String> {
+    state
+        .registry
+        .with_rhythm(MULTI_TEMPORAL, |rhythm: &mut MultiTemporalRhythm| Ok(rhythm.model()))
+}
+
+/// Switch between phase-only and Stuart-Landau amplitude dynamics.
+#[tauri::command]
+pub fn set_oscillator_model(model: OscillatorModel, state: State<AppState>) -> Result<(), String> {
+    state.registry.with_rhythm(MULTI_TEMPORAL, |rhythm: &mut MultiTemporalRhythm| {
+        rhythm.set_model(model);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn get_coupling(state: State<AppState>) -> Result<CouplingMatrix, String> {
+    with_coupling(&state, |_| Ok(()))
+}
+
+/// Couple the oscillators through an explicit matrix; `weights[i][j]` is how
+/// strongly oscillator `j` drives oscillator `i`. Lags and delays default to zero.
+#[tauri::command]
+pub fn set_coupling(
+    weights: Vec<Vec<f64>>,
+    lags: Option<Vec<Vec<f64>>>,
+    delays: Option<Vec<Vec<f64>>>,
+    state: State<AppState>,
+) -> Result<CouplingMatrix, String> {
+    let coupling = CouplingMatrix::new(weights, lags, delays)?;
+    with_coupling(&state, |rhythm| rhythm.set_coupling(coupling))
+}
+
+/// Couple the oscillators through a generated topology with a uniform phase
+/// lag and transmission delay.
+#[tauri::command]
+pub fn generate_coupling(
+    topology: Topology,
+    phase_lag: Option<f64>,
+    delay: Option<f64>,
+    state: State<AppState>,
+) -> Result<CouplingMatrix, String> {
+    with_coupling(&state, |rhythm| {
+        rhythm.generate_coupling(topology, phase_lag.unwrap_or(0.0), delay.unwrap_or(0.0))
+    })
+}
+
+/// Apply `f` to the multi-temporal rhythm and return its oscillators.
+fn with_oscillators(
+    state: &AppState,
+    f: impl FnOnce(&mut MultiTemporalRhythm) -> Result<(), String>,
+) -> Result<Vec<Oscillator>, String> {
+    state.registry.with_rhythm(MULTI_TEMPORAL, |rhythm: &mut MultiTemporalRhythm| {
+        f(rhythm)?;
+        Ok(rhythm.oscillators().to_vec())
+    })
+}
+
+/// Apply `f` to the multi-temporal rhythm and return its coupling matrix.
+fn with_coupling(
+    state: &AppState,
+    f: impl FnOnce(&mut MultiTemporalRhythm) -> Result<(), String>,
+) -> Result<CouplingMatrix, String> {
+    state.registry.with_rhythm(MULTI_TEMPORAL, |rhythm: &mut MultiTemporalRhythm| {
+        f(rhythm)?;
+        Ok(rhythm.coupling().clone())
+    })
+}
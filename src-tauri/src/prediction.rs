@@ -0,0 +1,85 @@
+use rhythm_core::{PredictionTension, PredictorConfig, ReleaseEvent, ReleaseShape, SignalSource};
+use tauri::State;
+
+use crate::AppState;
+
+const PREDICTION_TENSION: &str = "prediction_tension";
+
+#[tauri::command]
+pub fn get_prediction_input(state: State<AppState>) -> Result<SignalSource, String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| Ok(rhythm.input().clone()))
+}
+
+/// Predict a signal instead of simulating prediction errors; kind `none`
+/// goes back to simulation. A rhythm source must name one of its rhythm's
+/// current output channels.
+#[tauri::command]
+pub fn set_prediction_input(source: SignalSource, state: State<AppState>) -> Result<(), String> {
+    if let SignalSource::Rhythm { id, channel } = &source {
+        let channels = state.registry.get(id)?.lock().unwrap().output_values().len();
+        if *channel >= channels {
+            return Err(format!("Rhythm {} has {} channels, got channel {}", id, channels, channel));
+        }
+    }
+    set_input(&state, source)
+}
+
+/// Predict a series read from a file of numbers, played back at `sample_rate` Hz.
+#[tauri::command]
+pub fn load_prediction_series(path: String, sample_rate: f64, state: State<AppState>) -> Result<(), String> {
+    let source = SignalSource::load_series(path, sample_rate)?;
+    set_input(&state, source)
+}
+
+#[tauri::command]
+pub fn get_predictor(state: State<AppState>) -> Result<PredictorConfig, String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| Ok(rhythm.predictor()))
+}
+
+/// Replace the predictor with an untrained one.
+#[tauri::command]
+pub fn set_predictor(config: PredictorConfig, state: State<AppState>) -> Result<(), String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| rhythm.set_predictor(config))
+}
+
+#[tauri::command]
+pub fn get_release_shape(state: State<AppState>) -> Result<ReleaseShape, String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| Ok(rhythm.release_shape()))
+}
+
+#[tauri::command]
+pub fn set_release_shape(shape: ReleaseShape, state: State<AppState>) -> Result<(), String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| rhythm.set_release_shape(shape))
+}
+
+/// The most recent `limit` releases at every level, oldest first.
+#[tauri::command]
+pub fn get_releases(limit: Option<usize>, state: State<AppState>) -> Result<Vec<ReleaseEvent>, String> {
+    state.registry.with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| {
+        Ok(rhythm.releases().recent(limit.unwrap_or(usize::MAX)))
+    })
+}
+
+#[tauri::command]
+pub fn clear_releases(state: State<AppState>) -> Result<(), String> {
+    state.registry.with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| {
+        rhythm.clear_releases();
+        Ok(())
+    })
+}
+
+fn set_input(state: &AppState, source: SignalSource) -> Result<(), String> {
+    state
+        .registry
+        .with_rhythm(PREDICTION_TENSION, |rhythm: &mut PredictionTension| rhythm.set_input(source))
+}
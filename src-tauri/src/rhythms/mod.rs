@@ -1,11 +0,0 @@
-pub mod multi_temporal;
-pub mod critical_phi;
-pub mod prediction_tension;
-pub mod semantic_vortex;
-pub mod attention_wandering;
-
-pub use multi_temporal::MultiTemporalRhythm;
-pub use critical_phi::CriticalPhi;
-pub use prediction_tension::PredictionTension;
-pub use semantic_vortex::SemanticVortex;
-pub use attention_wandering::AttentionWandering;
\ No newline at end of file
@@ -0,0 +1,136 @@
+use rhythm_core::{RecordingHeader, ReplayStatus, SessionRecorder, SessionReplay};
+use tauri::{AppHandle, State};
+
+use crate::{timestamp, AppState};
+
+/// Record every frame of the listed rhythms, or of all rhythms, to a JSON
+/// Lines file at `path`. Any recording already in progress is finished first.
+#[tauri::command]
+pub fn start_recording(
+    path: String,
+    rhythms: Option<Vec<String>>,
+    app: AppHandle,
+    state: State<AppState>,
+) -> Result<RecordingHeader, String> {
+    let rhythm_ids = rhythms.unwrap_or_else(|| state.registry.ids());
+    let app_version = app.package_info().version.to_string();
+
+    let header = {
+        let clock = state.clock.lock().unwrap();
+        RecordingHeader::capture(&state.registry, &clock, &rhythm_ids, &app_version, timestamp())?
+    };
+    let recorder = SessionRecorder::create(path, &header)?;
+
+    state.recording_error.lock().unwrap().take();
+    let previous = state.recorder.lock().unwrap().replace(recorder);
+    if let Some(previous) = previous {
+        previous.finish()?;
+    }
+    Ok(header)
+}
+
+/// Finish the current recording and return the number of frames written.
+///
+/// If the recording already ended because a frame could not be written,
+/// the error that ended it is returned instead.
+#[tauri::command]
+pub fn stop_recording(state: State<AppState>) -> Result<u64, String> {
+    let recorder = state.recorder.lock().unwrap().take();
+    match recorder {
+        Some(recorder) => recorder.finish(),
+        None => match state.recording_error.lock().unwrap().take() {
+            Some(e) => Err(format!("Recording stopped: {}", e)),
+            None => Err("No recording in progress".to_string()),
+        },
+    }
+}
+
+/// Load a recorded session and play it back in place of the live simulation.
+///
+/// The live rhythms are frozen until the replay is stopped.
+#[tauri::command]
+pub fn start_replay(
+    path: String,
+    speed: Option<f64>,
+    looping: Option<bool>,
+    state: State<AppState>,
+) -> Result<RecordingHeader, String> {
+    let mut replay = SessionReplay::load(path)?;
+    if let Some(speed) = speed {
+        replay.set_speed(speed)?;
+    }
+    replay.set_looping(looping.unwrap_or(false));
+
+    let header = replay.header.clone();
+    *state.replay.lock().unwrap() = Some(replay);
+    state.subscriptions.reset();
+    Ok(header)
+}
+
+#[tauri::command]
+pub fn stop_replay(state: State<AppState>) {
+    *state.replay.lock().unwrap() = None;
+    state.subscriptions.reset();
+}
+
+#[tauri::command]
+pub fn get_replay_status(state: State<AppState>) -> Option<ReplayStatus> {
+    state.replay.lock().unwrap().as_ref().map(SessionReplay::status)
+}
+
+/// Jump to `position` seconds of simulated time and push the frames there.
+#[tauri::command]
+pub fn seek_replay(position: f64, state: State<AppState>) -> Result<ReplayStatus, String> {
+    // The clock is read first; the replay lock is never held while taking it
+    let tick_rate = state.clock.lock().unwrap().tick_rate;
+    let mut replay = state.replay.lock().unwrap();
+    let replay = replay.as_mut().ok_or_else(no_replay)?;
+    replay.seek(position)?;
+
+    state.subscriptions.reset();
+    state.subscriptions.publish(&*replay, replay.ticks(), tick_rate);
+    Ok(replay.status())
+}
+
+#[tauri::command]
+pub fn set_replay_speed(speed: f64, state: State<AppState>) -> Result<ReplayStatus, String> {
+    with_replay(&state, |replay| replay.set_speed(speed))
+}
+
+#[tauri::command]
+pub fn set_replay_looping(looping: bool, state: State<AppState>) -> Result<ReplayStatus, String> {
+    with_replay(&state, |replay| {
+        replay.set_looping(looping);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn pause_replay(state: State<AppState>) -> Result<ReplayStatus, String> {
+    with_replay(&state, |replay| {
+        replay.set_paused(true);
+        Ok(())
+    })
+}
+
+#[tauri::command]
+pub fn resume_replay(state: State<AppState>) -> Result<ReplayStatus, String> {
+    with_replay(&state, |replay| {
+        replay.set_paused(false);
+        Ok(())
+    })
+}
+
+fn with_replay(
+    state: &AppState,
+    f: impl FnOnce(&mut SessionReplay) -> Result<(), String>,
+) -> Result<ReplayStatus, String> {
+    let mut replay = state.replay.lock().unwrap();
+    let replay = replay.as_mut().ok_or_else(no_replay)?;
+    f(replay)?;
+    Ok(replay.status())
+}
+
+fn no_replay() -> String {
+    "No replay is loaded".to_string()
+}
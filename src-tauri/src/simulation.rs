@@ -0,0 +1,54 @@
+use std::time::Duration;
+
+use tokio::time::{sleep_until, Instant};
+
+use crate::AppState;
+
+/// Drive every registered rhythm from the backend at the clock's tick rate.
+///
+/// The loop keeps running while no visualizer is mounted. Ticks that are
+/// missed because the process was suspended are dropped rather than replayed
+/// in a burst. After each tick, frames are recorded and pushed to subscribed
+/// windows. While a replay is loaded it advances instead of the live rhythms.
+pub fn spawn(state: AppState) {
+    tauri::async_runtime::spawn(async move {
+        let mut next_tick = Instant::now();
+
+        loop {
+            let (interval, tick_rate) = {
+                let clock = state.clock.lock().unwrap();
+                (Duration::from_secs_f64(clock.tick_interval()), clock.tick_rate)
+            };
+            next_tick += interval;
+
+            let now = Instant::now();
+            if next_tick < now {
+                next_tick = now + interval;
+            }
+            sleep_until(next_tick).await;
+
+            {
+                let mut replay = state.replay.lock().unwrap();
+                if let Some(replay) = replay.as_mut() {
+                    replay.advance(interval.as_secs_f64());
+                    state.subscriptions.publish(&*replay, replay.ticks(), tick_rate);
+                    continue;
+                }
+            }
+
+            let ticked = {
+                let mut clock = state.clock.lock().unwrap();
+                if clock.paused {
+                    None
+                } else {
+                    clock.tick(&state.registry);
+                    Some(clock.clone())
+                }
+            };
+
+            if let Some(clock) = ticked {
+                state.after_tick(&clock);
+            }
+        }
+    });
+}
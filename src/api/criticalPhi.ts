@@ -0,0 +1,84 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+// One completed avalanche of the critical_phi rhythm; times in s
+export interface Avalanche {
+  start_time: number
+  duration: number
+  // Largest excursion of phi from its target, or most nodes active at once
+  peak: number
+  // Excursion or node activity integrated over the avalanche
+  size: number
+}
+
+// threshold: phi crossing avalanche_threshold; branching and sandpile run on the integration matrix
+export type CriticalityEngine = 'threshold' | 'branching' | 'sandpile'
+
+// p(x) ∝ x^-exponent for x ≥ x_min
+export interface PowerLawFit {
+  exponent: number
+  exponent_error: number
+  x_min: number
+  tail_count: number
+  ks_distance: number
+  // Optimistic, since the law is fitted to the same data; small values reject it
+  p_value: number
+}
+
+export interface HistogramBin {
+  lower: number
+  upper: number
+  count: number
+}
+
+export interface AvalancheStatistics {
+  count: number
+  // null with fewer than ten avalanches
+  size_fit: PowerLawFit | null
+  duration_fit: PowerLawFit | null
+  // 1 at criticality; null before any avalanche
+  branching_ratio: number | null
+  size_histogram: HistogramBin[]
+  duration_histogram: HistogramBin[]
+}
+
+export const avalanches = {
+  // Oldest first; all retained avalanches when `limit` is left out
+  get: (limit?: number) => invoke<Avalanche[]>('get_avalanches', { limit }),
+  statistics: () => invoke<AvalancheStatistics>('get_avalanche_statistics'),
+  clear: () => invoke<void>('clear_avalanches'),
+}
+
+export const criticalityEngine = {
+  get: () => invoke<CriticalityEngine>('get_criticality_engine'),
+  // The avalanche log starts over
+  set: (engine: CriticalityEngine) => invoke<void>('set_criticality_engine', { engine }),
+}
+
+export type WeightDistribution =
+  // Bounds in [0, 1]
+  | { kind: 'uniform'; low: number; high: number }
+  | { kind: 'normal'; mean: number; std_dev: number }
+  | { kind: 'log_normal'; mu: number; sigma: number }
+
+// Rates per second, at most 100; only existing links change
+export type Plasticity =
+  | { kind: 'frozen' }
+  | { kind: 'noise'; rate: number }
+  | { kind: 'hebbian'; learning_rate: number; decay: number }
+  | { kind: 'homeostatic'; rate: number }
+
+export interface MatrixConfig {
+  size: number
+  distribution: WeightDistribution
+  // Fraction of links left out
+  sparsity: number
+  // Whether each node also influences itself; on by default
+  self_links: boolean
+  plasticity: Plasticity
+}
+
+export const integrationMatrix = {
+  getConfig: () => invoke<MatrixConfig>('get_matrix_config'),
+  // Resolves with the new weights; fields left out take their defaults
+  configure: (config: Partial<MatrixConfig>) => invoke<number[][]>('configure_matrix', { config }),
+}
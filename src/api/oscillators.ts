@@ -0,0 +1,59 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+// One oscillator of the multi_temporal rhythm; frequency in Hz, phase in rad
+export interface Oscillator {
+  frequency: number
+  phase: number
+  amplitude: number
+  chaos_factor: number
+}
+
+export type OscillatorModel = 'phase_only' | 'stuart_landau'
+
+export type Topology =
+  | { kind: 'all_to_all' }
+  | { kind: 'ring'; neighbors: number }
+  | { kind: 'small_world'; neighbors: number; rewiring: number }
+  | { kind: 'scale_free'; attachments: number }
+  | { kind: 'hierarchical'; scale: number; asymmetry: number }
+
+// weights[i][j] is how strongly oscillator j drives oscillator i; lags in rad, delays in s
+export interface CouplingMatrix {
+  weights: number[][]
+  lags: number[][]
+  // null when the matrix was set directly
+  topology: Topology | null
+  phase_lag: number
+  delays: number[][]
+  delay: number
+}
+
+// Every call resolves with the full list of oscillators after the change
+export const oscillators = {
+  get: () => invoke<Oscillator[]>('get_oscillators'),
+  add: (oscillator: Oscillator) => invoke<Oscillator[]>('add_oscillator', { oscillator }),
+  remove: (index: number) => invoke<Oscillator[]>('remove_oscillator', { index }),
+  // Fields left out keep their value
+  update: (index: number, update: Partial<Oscillator>) =>
+    invoke<Oscillator[]>('update_oscillator', { index, update }),
+  load: (path: string) => invoke<Oscillator[]>('load_oscillator_bank', { path }),
+  save: (path: string) => invoke<void>('save_oscillator_bank', { path }),
+  // `count` time scales spaced evenly on a log scale between the two frequencies
+  generate: (count: number, highestFrequency: number, lowestFrequency: number) =>
+    invoke<Oscillator[]>('generate_oscillator_bank', { count, highestFrequency, lowestFrequency }),
+}
+
+export const oscillatorModel = {
+  get: () => invoke<OscillatorModel>('get_oscillator_model'),
+  // Every oscillator restarts from unit radius
+  set: (model: OscillatorModel) => invoke<void>('set_oscillator_model', { model }),
+}
+
+export const coupling = {
+  get: () => invoke<CouplingMatrix>('get_coupling'),
+  // Lags and delays default to zero
+  set: (weights: number[][], lags?: number[][], delays?: number[][]) =>
+    invoke<CouplingMatrix>('set_coupling', { weights, lags, delays }),
+  generate: (topology: Topology, phaseLag?: number, delay?: number) =>
+    invoke<CouplingMatrix>('generate_coupling', { topology, phaseLag, delay }),
+}
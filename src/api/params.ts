@@ -0,0 +1,22 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+export interface ParameterSpec {
+  name: string
+  description: string
+  default: number
+  min: number
+  max: number
+  unit: string
+}
+
+export type RhythmParams = Record<string, number>
+
+export const rhythmParams = {
+  schema: (rhythmType: string) =>
+    invoke<ParameterSpec[]>('get_parameter_schema', { rhythmType }),
+  get: (rhythmType: string) =>
+    invoke<RhythmParams>('get_rhythm_params', { rhythmType }),
+  // Partial updates are allowed; the whole update is rejected if any value is invalid
+  set: (rhythmType: string, params: Partial<RhythmParams>) =>
+    invoke<RhythmParams>('set_rhythm_params', { rhythmType, params }),
+}
@@ -0,0 +1,68 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+// Signal the prediction_tension rhythm predicts; `none` simulates prediction errors instead
+export type SignalSource =
+  | { kind: 'none' }
+  // Output channel of another rhythm, e.g. { id: 'multi_temporal', channel: 0 }
+  | { kind: 'rhythm'; id: string; channel: number }
+  // Looped playback at sample_rate Hz
+  | { kind: 'series'; samples: number[]; sample_rate: number }
+  | { kind: 'sine'; frequency: number; amplitude: number; noise: number }
+  // volatility in 1/√s
+  | { kind: 'random_walk'; volatility: number; noise: number }
+
+export type PredictorConfig =
+  // forgetting in (0, 1]
+  | { kind: 'autoregressive'; order: number; forgetting: number }
+  | { kind: 'kalman'; process_noise: number; measurement_noise: number }
+
+// Every shape fades under an envelope decaying with the release_decay_time parameter
+export type ReleaseShape =
+  | { kind: 'exponential' }
+  // Raised-cosine waves at frequency Hz
+  | { kind: 'oscillatory'; frequency: number }
+  // Random pulses at rate per second, each fading with time constant pulse_time seconds
+  | { kind: 'burst'; rate: number; pulse_time: number }
+
+export interface ReleaseEvent {
+  // 1 for the first hierarchy level
+  level: number
+  // Simulated seconds
+  start_time: number
+  duration: number
+  peak: number
+  pre_release_tension: number
+}
+
+// Reported as `prediction` in the rhythm's metadata
+export interface PredictionStep {
+  observation: number
+  prediction: number
+  variance: number
+  error: number
+  precision: number
+  // Precision-weighted squared error; about 1/2 when the predicted variance is right
+  surprise: number
+}
+
+export const predictionInput = {
+  get: () => invoke<SignalSource>('get_prediction_input'),
+  // The predictor and error buffer start over
+  set: (source: SignalSource) => invoke<void>('set_prediction_input', { source }),
+  // File holds a JSON array or numbers separated by whitespace or commas
+  load: (path: string, sampleRate: number) => invoke<void>('load_prediction_series', { path, sampleRate }),
+}
+
+export const predictor = {
+  get: () => invoke<PredictorConfig>('get_predictor'),
+  set: (config: PredictorConfig) => invoke<void>('set_predictor', { config }),
+}
+
+export const releases = {
+  // Oldest first; all retained releases when `limit` is left out
+  get: (limit?: number) => invoke<ReleaseEvent[]>('get_releases', { limit }),
+  clear: () => invoke<void>('clear_releases'),
+  getShape: () => invoke<ReleaseShape>('get_release_shape'),
+  // Applies to releases that start afterwards
+  setShape: (shape: ReleaseShape) => invoke<void>('set_release_shape', { shape }),
+}
@@ -0,0 +1,53 @@
+import { invoke } from '@tauri-apps/api/tauri'
+import type { ParameterSpec } from './params'
+import type { IntegrationScheme } from './simulation'
+
+export interface ChannelSpec {
+  name: string
+  description: string
+  unit: string
+  min: number
+  max: number
+}
+
+export type FieldType =
+  | 'number'
+  | 'integer'
+  | 'boolean'
+  | 'string'
+  | 'number_array'
+  | 'point_array'
+  | 'matrix'
+  | 'object'
+
+export interface MetadataField {
+  name: string
+  description: string
+  field_type: FieldType
+  unit: string
+}
+
+export interface RhythmDescription {
+  rhythm_type: string
+  output: {
+    channels: ChannelSpec[]
+    metadata: MetadataField[]
+  }
+  parameters: ParameterSpec[]
+  integration_scheme: IntegrationScheme | null
+}
+
+export interface LabeledRhythmData {
+  rhythm_type: string
+  timestamp: number
+  values: Record<string, number>
+  metadata: any
+}
+
+export const rhythmSchema = {
+  list: () => invoke<string[]>('list_rhythms'),
+  describe: (rhythmType: string) =>
+    invoke<RhythmDescription>('describe_rhythm', { rhythmType }),
+  labeledFrame: (rhythmType: string) =>
+    invoke<LabeledRhythmData>('get_labeled_rhythm_data', { rhythmType }),
+}
@@ -0,0 +1,43 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+export interface ReplayStatus {
+  position: number
+  start_time: number
+  end_time: number
+  speed: number
+  looping: boolean
+  paused: boolean
+}
+
+// Header line of a session file; see RecordingHeader in rhythm-core
+export interface RecordingHeader {
+  format: string
+  version: number
+  app_version: string
+  created_by: string
+  created_at: number
+  master_seed: number
+  clock: any
+  rhythms: Record<string, { seed: number; params: Record<string, number>; description: any }>
+}
+
+export const recording = {
+  // Records every rhythm when `rhythms` is omitted
+  start: (path: string, rhythms?: string[]) =>
+    invoke<RecordingHeader>('start_recording', { path, rhythms }),
+  // Resolves with the number of frames written; rejects with the write error
+  // if the recording already stopped on its own
+  stop: () => invoke<number>('stop_recording'),
+}
+
+export const replay = {
+  start: (path: string, speed?: number, looping?: boolean) =>
+    invoke<RecordingHeader>('start_replay', { path, speed, looping }),
+  stop: () => invoke<void>('stop_replay'),
+  status: () => invoke<ReplayStatus | null>('get_replay_status'),
+  seek: (position: number) => invoke<ReplayStatus>('seek_replay', { position }),
+  setSpeed: (speed: number) => invoke<ReplayStatus>('set_replay_speed', { speed }),
+  setLooping: (looping: boolean) => invoke<ReplayStatus>('set_replay_looping', { looping }),
+  pause: () => invoke<ReplayStatus>('pause_replay'),
+  resume: () => invoke<ReplayStatus>('resume_replay'),
+}
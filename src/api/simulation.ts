@@ -0,0 +1,34 @@
+import { invoke } from '@tauri-apps/api/tauri'
+
+export interface SimulationClock {
+  tick_rate: number
+  time_scale: number
+  paused: boolean
+  sim_time: number
+  tick_count: number
+}
+
+export const simulationClock = {
+  get: () => invoke<SimulationClock>('get_clock_state'),
+  pause: () => invoke<void>('pause_simulation'),
+  resume: () => invoke<void>('resume_simulation'),
+  // At most 10000 steps per call
+  step: (steps = 1) => invoke<SimulationClock>('step_simulation', { steps }),
+  setTimeScale: (timeScale: number) => invoke<void>('set_time_scale', { timeScale }),
+  setTickRate: (tickRate: number) => invoke<void>('set_tick_rate', { tickRate }),
+}
+
+export type IntegrationScheme = 'euler' | 'rk4' | 'semi_implicit'
+
+export const integration = {
+  get: (rhythmType: string) =>
+    invoke<IntegrationScheme | null>('get_integration_scheme', { rhythmType }),
+  set: (rhythmType: string, scheme: IntegrationScheme) =>
+    invoke<void>('set_integration_scheme', { rhythmType, scheme }),
+}
+
+export const snapshots = {
+  save: (path: string) => invoke<void>('save_snapshot', { path }),
+  // Resolves with the restored clock state; rejected while recording
+  load: (path: string) => invoke<SimulationClock>('load_snapshot', { path }),
+}
@@ -1,5 +1,7 @@
-import { useState, useEffect, useRef } from 'react'
+import { useState, useEffect } from 'react'
 import { invoke } from '@tauri-apps/api/tauri'
+import { getCurrent } from '@tauri-apps/api/window'
+import type { UnlistenFn } from '@tauri-apps/api/event'
 
 export interface RhythmData {
   rhythm_type: string
@@ -8,47 +10,61 @@ export interface RhythmData {
   metadata: any
 }
 
-export function useRhythm(rhythmType: string) {
+interface SubscriptionInfo {
+  id: number
+  event: string
+  rhythm_type: string
+}
+
+// Frames per second requested from the backend; undefined means every tick
+export function useRhythm(rhythmType: string, rate?: number) {
   const [data, setData] = useState<RhythmData | null>(null)
-  const animationFrameRef = useRef<number>()
-  const lastTimeRef = useRef<number>(Date.now())
 
   useEffect(() => {
     let mounted = true
+    let subscriptionId: number | null = null
+    let unlisten: UnlistenFn | null = null
 
-    const update = async () => {
-      const currentTime = Date.now()
-      const deltaTime = (currentTime - lastTimeRef.current) / 1000
-      lastTimeRef.current = currentTime
+    const cleanup = () => {
+      unlisten?.()
+      unlisten = null
+      if (subscriptionId !== null) {
+        invoke('unsubscribe_rhythm', { id: subscriptionId }).catch(() => {})
+        subscriptionId = null
+      }
+    }
 
+    const start = async () => {
       try {
-        // Update rhythm state in backend
-        await invoke('update_rhythm', { rhythmType, deltaTime })
-        
-        // Get current state
+        // Show the current state right away, even while the clock is paused
         const rhythmData = await invoke<RhythmData>('get_rhythm_data', { rhythmType })
-        
         if (mounted) {
           setData(rhythmData)
         }
-      } catch (error) {
-        console.error('Error updating rhythm:', error)
-      }
 
-      if (mounted) {
-        animationFrameRef.current = requestAnimationFrame(update)
+        const subscription = await invoke<SubscriptionInfo>('subscribe_rhythm', { rhythmType, rate })
+        subscriptionId = subscription.id
+        unlisten = await getCurrent().listen<RhythmData>(subscription.event, (event) => {
+          if (mounted) {
+            setData(event.payload)
+          }
+        })
+
+        if (!mounted) {
+          cleanup()
+        }
+      } catch (error) {
+        console.error('Error subscribing to rhythm:', error)
       }
     }
 
-    update()
+    start()
 
     return () => {
       mounted = false
-      if (animationFrameRef.current) {
-        cancelAnimationFrame(animationFrameRef.current)
-      }
+      cleanup()
     }
-  }, [rhythmType])
+  }, [rhythmType, rate])
 
   return data
-}
\ No newline at end of file
+}
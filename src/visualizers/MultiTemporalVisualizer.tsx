@@ -6,6 +6,8 @@ import { useRhythm } from '../hooks/useRhythm'
 export default function MultiTemporalVisualizer() {
   const meshRefs = useRef<THREE.Mesh[]>([])
   const data = useRhythm('multi_temporal')
+  // The oscillator bank can be resized at runtime
+  const indices = Array.from({ length: data?.values.length ?? 3 }, (_, i) => i)
 
   useFrame((state) => {
     if (!data) return
@@ -56,7 +58,7 @@ export default function MultiTemporalVisualizer() {
       </mesh>
       
       {/* Oscillators */}
-      {[0, 1, 2].map((index) => (
+      {indices.map((index) => (
         <mesh
           key={index}
           ref={(el) => {
@@ -77,8 +79,8 @@ export default function MultiTemporalVisualizer() {
       {/* Connection lines */}
       {data && data.metadata.coupling_strength && (
         <group>
-          {[0, 1, 2].map((i) => (
-            [0, 1, 2].filter(j => j !== i).map(j => (
+          {indices.map((i) => (
+            indices.filter(j => j !== i).map(j => (
               <line key={`${i}-${j}`}>
                 <bufferGeometry>
                   <bufferAttribute